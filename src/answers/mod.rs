@@ -0,0 +1,259 @@
+use crate::common::{self, AocError, BigInt, Result};
+use crate::solution::{Answer, PartResult};
+use std::collections::HashMap;
+
+/// Known correct answers, read from a file like:
+///
+/// ```toml
+/// [day_1]
+/// part_1 = 595
+/// part_2 = "cypueihajytordkgzxfqplbwn"
+/// ```
+///
+/// Only integers and basic strings are supported, with `\n` splitting a multi-line answer.
+/// Integers too large for an `i64` are kept as text, the same way solvers give them.
+#[derive(PartialEq, Debug, Default)]
+pub struct Answers {
+    known: HashMap<(u8, u8), Answer>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail(Answer),
+    Unknown,
+}
+
+fn parse_key(text: &str, prefix: &str) -> Option<u8> {
+    text.strip_prefix(prefix)?.parse().ok()
+}
+
+/// Reads a double-quoted string, supporting the `\n`, `\"` and `\\` escapes.
+pub(crate) fn parse_string(line: &str, value: &str) -> Result<String> {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(|| AocError::invalid(line, "Strings must be wrapped in double quotes"))?;
+
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            _ => return Err(AocError::invalid(line, "Unsupported escape sequence")),
+        }
+    }
+
+    Ok(result)
+}
+
+fn parse_answer(line: &str, value: &str) -> Result<Answer> {
+    if let Ok(integer) = value.parse::<BigInt>() {
+        return Ok(integer.into());
+    }
+
+    let text = parse_string(line, value)?;
+    if text.contains('\n') {
+        Ok(Answer::MultiLine(text.lines().map(String::from).collect()))
+    } else {
+        Ok(Answer::Text(text))
+    }
+}
+
+impl Answers {
+    pub fn parse(lines: &[String]) -> Result<Answers> {
+        Answers::parse_from(lines, None)
+    }
+
+    /// Parses answers for a single day, where the `[day_N]` table can be left out.
+    pub fn parse_for_day(lines: &[String], day: u8) -> Result<Answers> {
+        Answers::parse_from(lines, Some(day))
+    }
+
+    fn parse_from(lines: &[String], mut day: Option<u8>) -> Result<Answers> {
+        let mut known = HashMap::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            let at_line = |e: AocError| e.at_line(i + 1);
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = trimmed.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+                day = Some(parse_key(table.trim(), "day_").ok_or_else(|| {
+                    at_line(AocError::invalid(line, "Expected a table like [day_1]"))
+                })?);
+                continue;
+            }
+
+            let (key, value) = match trimmed.find('=') {
+                Some(idx) => (trimmed[..idx].trim(), trimmed[idx + 1..].trim()),
+                None => return Err(at_line(AocError::invalid(line, "Expected part_N = answer"))),
+            };
+            let part = parse_key(key, "part_")
+                .filter(|p| *p == 1 || *p == 2)
+                .ok_or_else(|| at_line(AocError::invalid(line, "Expected part_1 or part_2")))?;
+            let day = day.ok_or_else(|| {
+                at_line(AocError::invalid(
+                    line,
+                    "Answer is not inside a [day_N] table",
+                ))
+            })?;
+
+            known.insert((day, part), parse_answer(line, value).map_err(at_line)?);
+        }
+
+        Ok(Answers { known })
+    }
+
+    pub fn load(path: &str) -> Result<Answers> {
+        let lines = common::read_lines(path)?;
+        Answers::parse(&lines).map_err(|e| e.in_file(path))
+    }
+
+    pub fn load_for_day(path: &str, day: u8) -> Result<Answers> {
+        let lines = common::read_lines(path)?;
+        Answers::parse_for_day(&lines, day).map_err(|e| e.in_file(path))
+    }
+
+    /// The parts with a known answer for the day, in order.
+    pub fn parts(&self, day: u8) -> Vec<u8> {
+        let mut parts: Vec<u8> = self
+            .known
+            .keys()
+            .filter(|(d, _)| *d == day)
+            .map(|(_, p)| *p)
+            .collect();
+        parts.sort_unstable();
+        parts
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.known.get(&(day, part))
+    }
+
+    pub fn check(&self, result: &PartResult) -> Verdict {
+        match self.get(result.day, result.part) {
+            Some(expected) if *expected == result.answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn should_parse_answers() {
+        let input = lines(
+            "# Known answers\n\
+             [day_1]\n\
+             part_1 = 595\n\
+             part_2 = -12\n\
+             \n\
+             [day_2]\n\
+             part_2 = \"cypue\"\n\
+             [day_10]\n\
+             part_1 = \"#..\\n.#.\"",
+        );
+
+        let answers = Answers::parse(&input).unwrap();
+
+        assert_eq!(Some(&Answer::Integer(595)), answers.get(1, 1));
+        assert_eq!(Some(&Answer::Integer(-12)), answers.get(1, 2));
+        assert_eq!(None, answers.get(2, 1));
+        assert_eq!(Some(&Answer::Text("cypue".to_string())), answers.get(2, 2));
+        assert_eq!(
+            Some(&Answer::MultiLine(vec![
+                "#..".to_string(),
+                ".#.".to_string()
+            ])),
+            answers.get(10, 1)
+        );
+    }
+
+    #[test]
+    fn should_parse_answers_for_one_day() {
+        let answers = Answers::parse_for_day(&lines("part_2 = 10\npart_1 = 3"), 1).unwrap();
+
+        assert_eq!(Some(&Answer::Integer(3)), answers.get(1, 1));
+        assert_eq!(Some(&Answer::Integer(10)), answers.get(1, 2));
+        assert_eq!(vec![1, 2], answers.parts(1));
+        assert!(answers.parts(2).is_empty());
+    }
+
+    #[test]
+    fn should_reject_invalid_answers() {
+        assert!(Answers::parse(&lines("part_1 = 5")).is_err());
+        assert!(Answers::parse(&lines("[day_one]")).is_err());
+        assert!(Answers::parse(&lines("[day_1]\npart_3 = 5")).is_err());
+        assert!(Answers::parse(&lines("[day_1]\npart_1 = abc")).is_err());
+        assert!(Answers::parse(&lines("[day_1]\npart_1")).is_err());
+
+        let error = Answers::parse(&lines("[day_1]\npart_1 = \"a\\tb\"")).unwrap_err();
+        assert_eq!(
+            "2: Unsupported escape sequence: \"part_1 = \\\"a\\\\tb\\\"\"",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn should_check_integers_past_i64() {
+        let answers = Answers::parse(&lines("[day_1]\npart_1 = 18446744073709551614")).unwrap();
+        let big: BigInt = "18446744073709551614".parse().unwrap();
+        let result = |answer| PartResult {
+            day: 1,
+            part: 1,
+            answer,
+        };
+
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(&result(Answer::from(2 * i128::from(i64::MAX))))
+        );
+        assert_eq!(Verdict::Pass, answers.check(&result(Answer::from(big))));
+        assert_eq!(
+            Verdict::Fail(Answer::Text("18446744073709551614".to_string())),
+            answers.check(&result(Answer::from(i128::from(i64::MAX))))
+        );
+    }
+
+    #[test]
+    fn should_check_results() {
+        let answers = Answers::parse(&lines("[day_1]\npart_1 = 595\npart_2 = 3")).unwrap();
+        let result = |part, answer| PartResult {
+            day: 1,
+            part,
+            answer: Answer::Integer(answer),
+        };
+
+        assert_eq!(Verdict::Pass, answers.check(&result(1, 595)));
+        assert_eq!(
+            Verdict::Fail(Answer::Integer(3)),
+            answers.check(&result(2, 4))
+        );
+        assert_eq!(
+            Verdict::Unknown,
+            answers.check(&PartResult {
+                day: 2,
+                part: 1,
+                answer: Answer::Integer(1)
+            })
+        );
+    }
+}
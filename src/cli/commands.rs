@@ -0,0 +1,564 @@
+//! What each command does once its arguments are parsed. Every command prints its own output and
+//! errors, and gives back the code the process should exit with.
+
+use super::{
+    BenchOptions, FetchOptions, Format, LimitOptions, NewDayOptions, ReportOptions, RunOptions,
+    Selection, SolveOptions, SubmitOptions, VerifyOptions, WatchOptions,
+};
+use crate::answers::{Answers, Verdict};
+use crate::common::{self, BigInt, Result};
+use crate::day_1;
+use crate::day_1::frequency::{Frequency, FrequencyType};
+use crate::day_1::report::Report;
+use crate::examples::{self, Example};
+use crate::fetch::{self, Config, Fetched};
+use crate::json::Json;
+use crate::runner::{self, DayRun, Job, Limits, PartRun, Summary};
+use crate::scaffold;
+use crate::solution::{self, Answer, Day, PartResult};
+use crate::submit::{self, Attempt, History, Outcome};
+use crate::watch;
+use std::env;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Prints why a command failed, and gives the exit code for it.
+fn fail(message: impl Display) -> ExitCode {
+    eprintln!("{}", message);
+    ExitCode::FAILURE
+}
+
+fn exit_code(succeeded: bool) -> ExitCode {
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+pub fn list() -> ExitCode {
+    for day in solution::registry() {
+        println!("Day {:>2}: {}", day.number, day.title);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn report_part(result: &PartResult) {
+    println!(
+        "Day {} - Part {}: {}",
+        result.day, result.part, result.answer
+    );
+}
+
+/// One solved (or failed) part, as written out by `--format json`.
+fn part_json(
+    day: u8,
+    part: u8,
+    input_path: &str,
+    answer: Option<&Answer>,
+    duration: Option<Duration>,
+    error: Option<String>,
+) -> Json {
+    Json::object(vec![
+        ("day", Json::Integer(day.into())),
+        ("part", Json::Integer(part.into())),
+        ("answer", answer.map_or(Json::Null, Json::from)),
+        (
+            "duration_ms",
+            duration.map_or(Json::Null, |d| Json::Float(d.as_secs_f64() * 1000.0)),
+        ),
+        ("input", Json::from(input_path)),
+        ("error", Json::from(error)),
+    ])
+}
+
+/// The selected days, with day 1 swapped for one that tracks frequencies in the chosen type.
+fn select_days(
+    selection: &Selection,
+    frequency: Option<FrequencyType>,
+) -> std::result::Result<Vec<Day>, String> {
+    let mut days = match selection {
+        Selection::All => solution::registry(),
+        Selection::Day(number) => match solution::find(*number) {
+            Some(day) => vec![day],
+            None => return Err(format!("Day {} has not been solved yet", number)),
+        },
+    };
+
+    if let Some(frequency) = frequency {
+        for day in days.iter_mut().filter(|day| day.number == 1) {
+            *day = day_1::day(frequency);
+        }
+    }
+
+    Ok(days)
+}
+
+fn selected_parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    }
+}
+
+fn jobs(days: Vec<Day>, parts: &[u8], input: &Option<String>) -> Vec<Job> {
+    days.into_iter()
+        .map(|day| Job {
+            day,
+            parts: parts.to_vec(),
+            input_path: input.clone().unwrap_or_else(|| day.input_path()),
+        })
+        .collect()
+}
+
+fn workers(requested: Option<usize>) -> usize {
+    requested.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+}
+
+fn limits(options: &LimitOptions) -> std::result::Result<Limits, String> {
+    let isolate = if options.isolate {
+        match env::current_exe() {
+            Ok(exe) => Some(exe),
+            Err(e) => {
+                return Err(format!(
+                    "Could not find this executable to isolate solvers: {}",
+                    e
+                ))
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(Limits {
+        timeout: options.timeout,
+        isolate,
+        memory_mb: options.memory_mb,
+    })
+}
+
+/// Prints the requested parts of a day.
+fn report_day(job: &Job, run: Result<DayRun>, time: bool) {
+    let day = job.day.number;
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("Day {}: {}", day, e);
+            return;
+        }
+    };
+
+    if time {
+        println!(
+            "Day {} - Read: {:.2?}, Parse: {:.2?}",
+            day, run.read, run.parse
+        );
+    }
+
+    for part in run.parts {
+        match part.result {
+            Ok(result) => report_part(&result),
+            Err(e) => eprintln!("Day {} - Part {}: {}", day, part.part, e),
+        }
+
+        if time {
+            println!(
+                "Day {} - Part {} took {:.2?}",
+                day, part.part, part.duration
+            );
+        }
+    }
+}
+
+/// Like `report_day`, but collects the results as JSON objects instead of printing them.
+fn report_day_json(job: &Job, run: Result<DayRun>, records: &mut Vec<Json>) {
+    let day = job.day.number;
+    let input_path = &job.input_path;
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            for p in job.parts.iter() {
+                let error = Some(e.to_string());
+                records.push(part_json(day, *p, input_path, None, None, error));
+            }
+            return;
+        }
+    };
+
+    for part in run.parts {
+        let (answer, error) = match part.result {
+            Ok(result) => (Some(result.answer), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        records.push(part_json(
+            day,
+            part.part,
+            input_path,
+            answer.as_ref(),
+            Some(part.duration),
+            error,
+        ));
+    }
+}
+
+pub fn run(options: RunOptions) -> ExitCode {
+    let parts = selected_parts(options.part);
+    let days = match select_days(&options.selection, options.frequency) {
+        Ok(days) => days,
+        Err(message) => return fail(message),
+    };
+    let limits = match limits(&options.limits) {
+        Ok(limits) => limits,
+        Err(message) => return fail(message),
+    };
+    let jobs = jobs(days, &parts, &options.input);
+    let runs = runner::solve_all(&jobs, workers(options.jobs), &limits);
+
+    let mut summary = Summary::default();
+    let mut records = Vec::new();
+    for (job, run) in jobs.iter().zip(runs) {
+        summary.record_run(job, &run);
+
+        match options.format {
+            Format::Text => report_day(job, run, options.time),
+            Format::Json => report_day_json(job, run, &mut records),
+        }
+    }
+
+    match options.format {
+        Format::Text => println!(
+            "Summary: {} succeeded, {} failed, {} panicked, {} timed out",
+            summary.succeeded, summary.failed, summary.panicked, summary.timed_out
+        ),
+        Format::Json => println!("{}", Json::Array(records)),
+    }
+
+    exit_code(summary.is_success())
+}
+
+pub fn bench(options: BenchOptions) -> ExitCode {
+    let parts = selected_parts(options.part);
+    let days = match select_days(&options.selection, options.frequency) {
+        Ok(days) => days,
+        Err(message) => return fail(message),
+    };
+
+    let mut succeeded = true;
+    for day in days.iter() {
+        match runner::bench_day(day, &parts, &day.input_path(), options.iterations) {
+            Ok(phases) => {
+                for (phase, stats) in phases {
+                    println!(
+                        "Day {} - {}: min {:.2?}, median {:.2?}, max {:.2?} ({} runs)",
+                        day.number, phase, stats.min, stats.median, stats.max, stats.runs
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("Day {}: {}", day.number, e);
+                succeeded = false;
+            }
+        }
+    }
+
+    exit_code(succeeded)
+}
+
+/// Checks a day against the known answers, returning false on any mismatch or error.
+fn verify_day(job: &Job, run: Result<DayRun>, answers: &Answers) -> bool {
+    let day = job.day.number;
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("Day {}: {}", day, e);
+            return false;
+        }
+    };
+
+    let mut succeeded = true;
+    for PartRun {
+        part: p, result, ..
+    } in run.parts
+    {
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                println!("Day {} - Part {}: error ({})", day, p, e);
+                succeeded = false;
+                continue;
+            }
+        };
+
+        match answers.check(&result) {
+            Verdict::Pass => println!("Day {} - Part {}: pass", day, p),
+            Verdict::Fail(expected) => {
+                println!(
+                    "Day {} - Part {}: FAIL (expected {}, got {})",
+                    day, p, expected, result.answer
+                );
+                succeeded = false;
+            }
+            Verdict::Unknown => {
+                println!("Day {} - Part {}: unknown (got {})", day, p, result.answer)
+            }
+        }
+    }
+
+    succeeded
+}
+
+pub fn verify(options: VerifyOptions) -> ExitCode {
+    let answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(e) => return fail(e),
+    };
+    let days = match select_days(&options.selection, options.frequency) {
+        Ok(days) => days,
+        Err(message) => return fail(message),
+    };
+    let limits = match limits(&options.limits) {
+        Ok(limits) => limits,
+        Err(message) => return fail(message),
+    };
+
+    let jobs = jobs(days, &selected_parts(None), &None);
+    let runs = runner::solve_all(&jobs, workers(options.jobs), &limits);
+
+    let mut succeeded = true;
+    for (job, run) in jobs.iter().zip(runs) {
+        succeeded &= verify_day(job, run, &answers);
+    }
+
+    exit_code(succeeded)
+}
+
+/// Reads, parses and solves a single part, leaving any panic to escape.
+fn solve_part(day: &Day, part: u8, input_path: &str) -> Result<PartResult> {
+    common::read_input(input_path)
+        .and_then(|lines| day.parse(lines).map_err(|e| e.in_file(input_path)))
+        .and_then(|input| day.solve(part, &input))
+}
+
+/// Solves a single part, printing only its answer. Panics are left to escape,
+/// so that a parent running this with `--isolate` can tell them apart from errors.
+pub fn solve(options: SolveOptions) -> ExitCode {
+    let day = match select_days(&Selection::Day(options.day), options.frequency) {
+        Ok(days) => days[0],
+        Err(message) => return fail(message),
+    };
+    let input_path = options.input.unwrap_or_else(|| day.input_path());
+
+    match solve_part(&day, options.part, &input_path) {
+        Ok(result) => {
+            println!("{}", result.answer);
+            ExitCode::SUCCESS
+        }
+        Err(e) => fail(e),
+    }
+}
+
+pub fn fetch(options: FetchOptions) -> ExitCode {
+    let path = solution::input_path(options.day);
+    let result = Config::load(&options.config)
+        .and_then(|config| fetch::fetch_input(&config, options.day, &path));
+
+    match result {
+        Ok(Fetched::Downloaded) => println!("Downloaded {}", path),
+        Ok(Fetched::Cached) => println!("{} already exists", path),
+        Err(e) => return fail(e),
+    }
+
+    ExitCode::SUCCESS
+}
+
+pub fn submit(options: SubmitOptions) -> ExitCode {
+    let day = match select_days(&Selection::Day(options.day), options.frequency) {
+        Ok(days) => days[0],
+        Err(message) => return fail(message),
+    };
+    let part = options.part;
+
+    let answer = match solve_part(&day, part, &day.input_path()) {
+        Ok(PartResult {
+            answer: Answer::MultiLine(_),
+            ..
+        }) => return fail("Multi-line answers have to be read and submitted by hand"),
+        Ok(result) => result.answer.to_string(),
+        Err(e) => return fail(e),
+    };
+    let history = match History::load(&options.history) {
+        Ok(history) => history,
+        Err(e) => return fail(e),
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    if !options.force {
+        if let Some(reason) = history.refusal(day.number, part, &answer, now) {
+            return fail(format!("Not submitting {}: {}", answer, reason));
+        }
+    }
+
+    let outcome = match Config::load(&options.config)
+        .and_then(|config| submit::submit(&config, day.number, part, &answer))
+    {
+        Ok(outcome) => outcome,
+        Err(e) => return fail(e),
+    };
+    let attempt = Attempt {
+        time: now,
+        day: day.number,
+        part,
+        answer,
+        outcome,
+    };
+    if let Err(e) = History::record(&options.history, &attempt) {
+        eprintln!("{}", e);
+    }
+
+    println!(
+        "Day {} - Part {}: {} is {}",
+        day.number, part, attempt.answer, attempt.outcome
+    );
+    exit_code(attempt.outcome == Outcome::Correct)
+}
+
+/// Prints whether each part of an example still gets its expected answer.
+fn report_example(example: &Example) {
+    let name = &example.name;
+    let checks = match example.check() {
+        Ok(checks) => checks,
+        Err(e) => {
+            eprintln!("Example {}: {}", name, e);
+            return;
+        }
+    };
+
+    for check in checks {
+        let p = check.part;
+        match (check.result, check.answer) {
+            (Ok(Verdict::Pass), _) => println!("Example {} - Part {}: pass", name, p),
+            (Ok(Verdict::Fail(expected)), Some(answer)) => println!(
+                "Example {} - Part {}: FAIL (expected {}, got {})",
+                name, p, expected, answer
+            ),
+            (Err(e), _) => println!("Example {} - Part {}: error ({})", name, p, e),
+            (Ok(_), _) => println!("Example {} - Part {}: unknown", name, p),
+        }
+    }
+}
+
+/// Solves the day with timings, then checks its examples.
+fn watch_run(job: &Job, limits: &Limits) {
+    report_day(
+        job,
+        runner::solve_day(&job.day, &job.parts, &job.input_path, limits),
+        true,
+    );
+
+    match examples::find(&job.day) {
+        Ok(found) => found.iter().for_each(report_example),
+        Err(e) => eprintln!("Day {}: {}", job.day.number, e),
+    }
+}
+
+pub fn watch(options: WatchOptions) -> ExitCode {
+    let parts = selected_parts(options.part);
+    let days = match select_days(&Selection::Day(options.day), options.frequency) {
+        Ok(days) => days,
+        Err(message) => return fail(message),
+    };
+    let limits = match limits(&options.limits) {
+        Ok(limits) => limits,
+        Err(message) => return fail(message),
+    };
+    let job = jobs(days, &parts, &None).remove(0);
+    let input_path = job.day.input_path();
+    let dir = Path::new(&input_path).parent().unwrap_or(Path::new("."));
+
+    println!("Watching {} for changes", dir.display());
+    watch_run(&job, &limits);
+
+    watch::watch(dir, options.interval, |changes| {
+        println!();
+        for path in changes {
+            println!("Changed {}", path.display());
+        }
+        watch_run(&job, &limits);
+    })
+}
+
+/// Reads the input for day 1's report, then reports on it in the chosen frequency type.
+pub fn report(options: ReportOptions) -> ExitCode {
+    if options.day != 1 {
+        return fail(format!("Only day 1 has a report, not day {}", options.day));
+    }
+    let day = match select_days(&Selection::Day(options.day), Some(options.frequency)) {
+        Ok(days) => days[0],
+        Err(message) => return fail(message),
+    };
+    let input_path = options.input.clone().unwrap_or_else(|| day.input_path());
+    let lines = match common::read_input(&input_path) {
+        Ok(lines) => lines,
+        Err(e) => return fail(e),
+    };
+
+    match options.frequency {
+        FrequencyType::I32 => report_in::<i32>(&options, &input_path, &lines),
+        FrequencyType::I64 => report_in::<i64>(&options, &input_path, &lines),
+        FrequencyType::I128 => report_in::<i128>(&options, &input_path, &lines),
+        FrequencyType::Big => report_in::<BigInt>(&options, &input_path, &lines),
+    }
+}
+
+/// Prints the report with frequencies tracked in `T`, and writes its trace if one was asked for.
+fn report_in<T: Frequency>(
+    options: &ReportOptions,
+    input_path: &str,
+    lines: &[String],
+) -> ExitCode {
+    let trace = options.trace.is_some();
+    let report = match Report::<T>::new(T::zero(), lines, options.follow, trace) {
+        Ok(report) => report,
+        Err(e) => return fail(e.in_file(input_path)),
+    };
+    print!("{}", report);
+
+    if let Some(path) = &options.trace {
+        let written = File::create(path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            report.write_trace(&mut out)?;
+            out.flush()
+        });
+        match written {
+            Ok(()) => println!("Wrote {} changes to {}", report.trace.len(), path),
+            Err(e) => return fail(format!("Could not write {}: {}", path, e)),
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+pub fn new_day(options: NewDayOptions) -> ExitCode {
+    match scaffold::new_day(Path::new("."), options.day, options.title.as_deref()) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Updated {}", path.display());
+            }
+            println!(
+                "Save the puzzle input as inputs/day_{}/input.txt, and examples alongside it in examples/",
+                options.day
+            );
+            ExitCode::SUCCESS
+        }
+        Err(message) => fail(message),
+    }
+}
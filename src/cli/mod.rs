@@ -0,0 +1,951 @@
+//! The `aoc` command line: its arguments, and the commands they run.
+
+use crate::day_1::frequency::FrequencyType;
+use std::time::Duration;
+pub mod commands;
+
+pub const USAGE: &str = "Usage:
+    aoc run --all [--time] [--format <text|json>] [--jobs <COUNT>]
+            [--frequency <TYPE>] [LIMITS]
+    aoc run --day <N> [--part <1|2>] [--input <PATH>] [--time] [--format <text|json>]
+            [--frequency <TYPE>] [LIMITS]
+    aoc bench [--day <N>] [--part <1|2>] [--iterations <COUNT>] [--frequency <TYPE>]
+    aoc verify [--day <N>] [--answers <PATH>] [--jobs <COUNT>] [--frequency <TYPE>] [LIMITS]
+    aoc solve --day <N> --part <1|2> [--input <PATH>] [--frequency <TYPE>]
+    aoc fetch --day <N> [--config <PATH>]
+    aoc submit --day <N> --part <1|2> [--config <PATH>] [--history <PATH>] [--force]
+               [--frequency <TYPE>]
+    aoc watch --day <N> [--part <1|2>] [--interval <SECONDS>] [--frequency <TYPE>] [LIMITS]
+    aoc report --day 1 [--input <PATH>] [--trace <PATH>] [--follow <CHANGES>]
+               [--frequency <TYPE>]
+    aoc list
+    aoc new-day <N> [--title <TITLE>]
+
+Options:
+    --day, -d <N>       Day to run
+    --part, -p <1|2>    Only run one part of the day
+    --input, -i <PATH>  Read the puzzle input from PATH instead, or from stdin if PATH is -
+    --all, -a           Run every registered day
+    --time, -t          Show how long reading, parsing and each part took
+    --format, -f <text|json>
+                        Print results as text lines or as a JSON array [default: text]
+    --iterations, -n <COUNT>
+                        Times to repeat each phase when benchmarking [default: 10]
+    --jobs, -j <COUNT>  Number of days to solve at once [default: one per CPU]
+    --answers <PATH>    Known answers to verify against [default: answers.toml]
+    --config <PATH>     Session token and website to download inputs with [default: aoc.toml]
+    --history <PATH>    Log of every submitted answer [default: submissions.log]
+    --force             Submit even if the history shows the answer is wrong
+    --interval <SECONDS>
+                        How often to check for changes when watching [default: 0.5]
+    --title <TITLE>     Puzzle title for a new day, which also names its solution type
+    --trace <PATH>      Write every change followed, and the frequency after it, as CSV
+    --follow <CHANGES>  Most changes to follow when reporting [default: 1000000]
+    --frequency <TYPE>  Number type for day 1's frequencies: i32, i64, or i128 and big for inputs
+                        that go past the range of i64, with big having no limit at all
+                        [default: i64]
+
+Limits:
+    --timeout <SECONDS> Give up on parsing, or on a part, after this long
+    --isolate           Solve each part in its own process, which is killed when it times out
+    --memory-limit <MB> Memory allowed to each part's process (Unix only, implies --isolate)
+
+`aoc fetch` downloads the day's input to inputs/day_N/input.txt, unless it is already there.
+The AOC_SESSION and AOC_BASE_URL environment variables override the config file.
+
+`aoc submit` solves the part, then posts its answer. Answers the history already shows to be
+wrong, or that are beyond one found too high or too low, are not sent.
+
+`aoc watch` solves the day, then solves it again, along with its examples, whenever
+a file in inputs/day_N/ changes. Stop it with Ctrl-C.
+
+`aoc report` describes how day 1's device behaves on the input: the drift per pass, the range
+of frequencies, how often each is visited, and where the first one repeats.
+
+`aoc new-day` creates src/day_N/mod.rs from a template along with inputs/day_N/,
+and registers the day. Run it from the root of the repository.
+
+`aoc solve` prints nothing but the answer, and is what --isolate runs for each part.
+
+Running with no arguments is the same as `aoc run --all`.";
+
+#[derive(PartialEq, Debug)]
+pub enum Selection {
+    All,
+    Day(u8),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(PartialEq, Debug, Default)]
+pub struct LimitOptions {
+    pub timeout: Option<Duration>,
+    pub isolate: bool,
+    pub memory_mb: Option<u64>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub part: Option<u8>,
+    pub input: Option<String>,
+    pub time: bool,
+    pub format: Format,
+    pub jobs: Option<usize>,
+    /// Overrides the number type day 1 tracks its frequencies in.
+    pub frequency: Option<FrequencyType>,
+    pub limits: LimitOptions,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct BenchOptions {
+    pub selection: Selection,
+    pub part: Option<u8>,
+    pub iterations: usize,
+    pub frequency: Option<FrequencyType>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct VerifyOptions {
+    pub selection: Selection,
+    pub answers: String,
+    pub jobs: Option<usize>,
+    pub frequency: Option<FrequencyType>,
+    pub limits: LimitOptions,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SolveOptions {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub frequency: Option<FrequencyType>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct FetchOptions {
+    pub day: u8,
+    pub config: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SubmitOptions {
+    pub day: u8,
+    pub part: u8,
+    pub config: String,
+    pub history: String,
+    pub force: bool,
+    pub frequency: Option<FrequencyType>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct WatchOptions {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub interval: Duration,
+    pub frequency: Option<FrequencyType>,
+    pub limits: LimitOptions,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ReportOptions {
+    pub day: u8,
+    pub input: Option<String>,
+    pub trace: Option<String>,
+    pub follow: usize,
+    pub frequency: FrequencyType,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NewDayOptions {
+    pub day: u8,
+    pub title: Option<String>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    Solve(SolveOptions),
+    Fetch(FetchOptions),
+    Submit(SubmitOptions),
+    Watch(WatchOptions),
+    Report(ReportOptions),
+    NewDay(NewDayOptions),
+    List,
+    Help,
+}
+
+fn value<'a>(
+    flag: &str,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<&'a String, String> {
+    args.next()
+        .ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn number(flag: &str, value: &str) -> Result<u8, String> {
+    value
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn part(flag: &str, value: &str) -> Result<u8, String> {
+    match number(flag, value)? {
+        p @ 1..=2 => Ok(p),
+        p => Err(format!("Part must be 1 or 2, not {}", p)),
+    }
+}
+
+fn count(flag: &str, value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("Invalid value for {}: {}", flag, value)),
+    }
+}
+
+fn seconds(flag: &str, value: &str) -> Result<Duration, String> {
+    match value.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(duration)) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!("Invalid value for {}: {}", flag, value)),
+    }
+}
+
+/// Checks `--frequency` is only given when day 1 is among the days selected.
+fn check_frequency(frequency: Option<FrequencyType>, selection: &Selection) -> Result<(), String> {
+    match (frequency, selection) {
+        (Some(_), Selection::Day(day)) if *day != 1 => {
+            Err("--frequency only applies to day 1".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Applies a flag to the limits, returning false if it isn't a limit flag at all.
+fn parse_limit<'a>(
+    arg: &str,
+    args: &mut impl Iterator<Item = &'a String>,
+    limits: &mut LimitOptions,
+) -> Result<bool, String> {
+    match arg {
+        "--timeout" => limits.timeout = Some(seconds(arg, value(arg, args)?)?),
+        "--isolate" => limits.isolate = true,
+        "--memory-limit" => {
+            limits.memory_mb = Some(count(arg, value(arg, args)?)? as u64);
+            limits.isolate = true;
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn parse_run(args: &[String]) -> Result<RunOptions, String> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut time = false;
+    let mut format = Format::Text;
+    let mut jobs = None;
+    let mut frequency = None;
+    let mut limits = LimitOptions::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if parse_limit(arg, &mut iter, &mut limits)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--all" | "-a" => all = true,
+            "--frequency" => frequency = Some(value(arg, &mut iter)?.parse()?),
+            "--day" | "-d" => day = Some(number(arg, value(arg, &mut iter)?)?),
+            "--part" | "-p" => part = Some(self::part(arg, value(arg, &mut iter)?)?),
+            "--input" | "-i" => input = Some(value(arg, &mut iter)?.to_string()),
+            "--time" | "-t" => time = true,
+            "--jobs" | "-j" => jobs = Some(count(arg, value(arg, &mut iter)?)?),
+            "--format" | "-f" => {
+                format = match value(arg, &mut iter)?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    let selection = match (all, day) {
+        (true, Some(_)) => return Err("--all cannot be combined with --day".to_string()),
+        (true, None) => Selection::All,
+        (false, Some(d)) => Selection::Day(d),
+        (false, None) => return Err("Either --day or --all is required".to_string()),
+    };
+
+    if selection == Selection::All && input.is_some() {
+        return Err("--input can only be used with a single --day".to_string());
+    }
+
+    if limits.isolate && input.as_deref() == Some("-") {
+        return Err("--isolate cannot read the input from stdin".to_string());
+    }
+
+    check_frequency(frequency, &selection)?;
+
+    Ok(RunOptions {
+        selection,
+        part,
+        input,
+        time,
+        format,
+        jobs,
+        frequency,
+        limits,
+    })
+}
+
+fn parse_bench(args: &[String]) -> Result<BenchOptions, String> {
+    let mut selection = Selection::All;
+    let mut part = None;
+    let mut iterations = 10;
+    let mut frequency = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" | "-d" => selection = Selection::Day(number(arg, value(arg, &mut iter)?)?),
+            "--part" | "-p" => part = Some(self::part(arg, value(arg, &mut iter)?)?),
+            "--iterations" | "-n" => iterations = count(arg, value(arg, &mut iter)?)?,
+            "--frequency" => frequency = Some(value(arg, &mut iter)?.parse()?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    check_frequency(frequency, &selection)?;
+    Ok(BenchOptions {
+        selection,
+        part,
+        iterations,
+        frequency,
+    })
+}
+
+fn parse_verify(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut selection = Selection::All;
+    let mut answers = "answers.toml".to_string();
+    let mut jobs = None;
+    let mut frequency = None;
+    let mut limits = LimitOptions::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if parse_limit(arg, &mut iter, &mut limits)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--day" | "-d" => selection = Selection::Day(number(arg, value(arg, &mut iter)?)?),
+            "--answers" => answers = value(arg, &mut iter)?.to_string(),
+            "--jobs" | "-j" => jobs = Some(count(arg, value(arg, &mut iter)?)?),
+            "--frequency" => frequency = Some(value(arg, &mut iter)?.parse()?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    check_frequency(frequency, &selection)?;
+    Ok(VerifyOptions {
+        selection,
+        answers,
+        jobs,
+        frequency,
+        limits,
+    })
+}
+
+fn parse_solve(args: &[String]) -> Result<SolveOptions, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut frequency = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(number(arg, value(arg, &mut iter)?)?),
+            "--part" | "-p" => part = Some(self::part(arg, value(arg, &mut iter)?)?),
+            "--input" | "-i" => input = Some(value(arg, &mut iter)?.to_string()),
+            "--frequency" => frequency = Some(value(arg, &mut iter)?.parse()?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    match (day, part) {
+        (Some(day), Some(part)) => {
+            check_frequency(frequency, &Selection::Day(day))?;
+            Ok(SolveOptions {
+                day,
+                part,
+                input,
+                frequency,
+            })
+        }
+        _ => Err("Both --day and --part are required".to_string()),
+    }
+}
+
+fn parse_fetch(args: &[String]) -> Result<FetchOptions, String> {
+    let mut day = None;
+    let mut config = "aoc.toml".to_string();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(number(arg, value(arg, &mut iter)?)?),
+            "--config" => config = value(arg, &mut iter)?.to_string(),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    match day {
+        Some(0) => Err("Days start at 1".to_string()),
+        Some(day) => Ok(FetchOptions { day, config }),
+        None => Err("--day is required".to_string()),
+    }
+}
+
+fn parse_submit(args: &[String]) -> Result<SubmitOptions, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut config = "aoc.toml".to_string();
+    let mut history = "submissions.log".to_string();
+    let mut force = false;
+    let mut frequency = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(number(arg, value(arg, &mut iter)?)?),
+            "--part" | "-p" => part = Some(self::part(arg, value(arg, &mut iter)?)?),
+            "--config" => config = value(arg, &mut iter)?.to_string(),
+            "--history" => history = value(arg, &mut iter)?.to_string(),
+            "--force" => force = true,
+            "--frequency" => frequency = Some(value(arg, &mut iter)?.parse()?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    match (day, part) {
+        (Some(day), Some(part)) => {
+            check_frequency(frequency, &Selection::Day(day))?;
+            Ok(SubmitOptions {
+                day,
+                part,
+                config,
+                history,
+                force,
+                frequency,
+            })
+        }
+        _ => Err("Both --day and --part are required".to_string()),
+    }
+}
+
+fn parse_watch(args: &[String]) -> Result<WatchOptions, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut interval = Duration::from_millis(500);
+    let mut frequency = None;
+    let mut limits = LimitOptions::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if parse_limit(arg, &mut iter, &mut limits)? {
+            continue;
+        }
+
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(number(arg, value(arg, &mut iter)?)?),
+            "--part" | "-p" => part = Some(self::part(arg, value(arg, &mut iter)?)?),
+            "--interval" => interval = seconds(arg, value(arg, &mut iter)?)?,
+            "--frequency" => frequency = Some(value(arg, &mut iter)?.parse()?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    match day {
+        Some(day) => {
+            check_frequency(frequency, &Selection::Day(day))?;
+            Ok(WatchOptions {
+                day,
+                part,
+                interval,
+                frequency,
+                limits,
+            })
+        }
+        None => Err("--day is required".to_string()),
+    }
+}
+
+fn parse_report(args: &[String]) -> Result<ReportOptions, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut trace = None;
+    let mut follow = 1_000_000;
+    let mut frequency = FrequencyType::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(number(arg, value(arg, &mut iter)?)?),
+            "--input" | "-i" => input = Some(value(arg, &mut iter)?.to_string()),
+            "--trace" => trace = Some(value(arg, &mut iter)?.to_string()),
+            "--follow" => follow = count(arg, value(arg, &mut iter)?)?,
+            "--frequency" => frequency = value(arg, &mut iter)?.parse()?,
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    match day {
+        Some(day) => Ok(ReportOptions {
+            day,
+            input,
+            trace,
+            follow,
+            frequency,
+        }),
+        None => Err("--day is required".to_string()),
+    }
+}
+
+fn parse_new_day(args: &[String]) -> Result<NewDayOptions, String> {
+    let mut day = None;
+    let mut title = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--title" => title = Some(value(arg, &mut iter)?.to_string()),
+            other if day.is_none() && !other.starts_with('-') => {
+                day = match number("day", other)? {
+                    0 => return Err("Days start at 1".to_string()),
+                    d => Some(d),
+                }
+            }
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+    }
+
+    match day {
+        Some(day) => Ok(NewDayOptions { day, title }),
+        None => Err("The number of the new day is required".to_string()),
+    }
+}
+
+/// Parses the command line arguments, excluding the program name.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    match args.split_first() {
+        None => Ok(Command::Run(RunOptions {
+            selection: Selection::All,
+            part: None,
+            input: None,
+            time: false,
+            format: Format::Text,
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions::default(),
+        })),
+        Some((command, rest)) => match command.as_str() {
+            "run" => parse_run(rest).map(Command::Run),
+            "bench" => parse_bench(rest).map(Command::Bench),
+            "verify" => parse_verify(rest).map(Command::Verify),
+            "solve" => parse_solve(rest).map(Command::Solve),
+            "fetch" => parse_fetch(rest).map(Command::Fetch),
+            "submit" => parse_submit(rest).map(Command::Submit),
+            "watch" => parse_watch(rest).map(Command::Watch),
+            "report" => parse_report(rest).map(Command::Report),
+            "new-day" => parse_new_day(rest).map(Command::NewDay),
+            "list" if rest.is_empty() => Ok(Command::List),
+            "list" => Err(format!("Unexpected argument: {}", rest[0])),
+            "help" | "--help" | "-h" => Ok(Command::Help),
+            other => Err(format!("Unknown command: {}", other)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn should_run_all_without_arguments() {
+        let expected = Command::Run(RunOptions {
+            selection: Selection::All,
+            part: None,
+            input: None,
+            time: false,
+            format: Format::Text,
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(Ok(expected), parse(&[]));
+    }
+
+    #[test]
+    fn should_parse_single_day() {
+        let expected = Command::Run(RunOptions {
+            selection: Selection::Day(4),
+            part: Some(2),
+            input: Some("path.txt".to_string()),
+            time: true,
+            format: Format::Text,
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args("run --day 4 --part 2 --input path.txt --time"))
+        );
+    }
+
+    #[test]
+    fn should_parse_json_format() {
+        let expected = Command::Run(RunOptions {
+            selection: Selection::All,
+            part: None,
+            input: None,
+            time: false,
+            format: Format::Json,
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("run --all --format json")));
+    }
+
+    #[test]
+    fn should_parse_stdin_input() {
+        let expected = Command::Run(RunOptions {
+            selection: Selection::Day(1),
+            part: None,
+            input: Some("-".to_string()),
+            time: false,
+            format: Format::Text,
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("run -d 1 -i -")));
+    }
+
+    #[test]
+    fn should_parse_frequency_type() {
+        let expected = Command::Run(RunOptions {
+            selection: Selection::Day(1),
+            part: None,
+            input: None,
+            time: false,
+            format: Format::Text,
+            jobs: None,
+            frequency: Some(FrequencyType::I128),
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("run -d 1 --frequency i128")));
+        assert!(parse(&args("run --all --frequency big")).is_ok());
+        assert!(parse(&args("run -d 1 --frequency i32")).is_ok());
+        assert_eq!(
+            Err("--frequency only applies to day 1".to_string()),
+            parse(&args("run -d 2 --frequency big"))
+        );
+        assert_eq!(
+            Err("Unknown frequency type: u8".to_string()),
+            parse(&args("run -d 1 --frequency u8"))
+        );
+        assert!(parse(&args("run -d 1 --frequency big --isolate")).is_ok());
+        assert_eq!(
+            Err("--frequency only applies to day 1".to_string()),
+            parse(&args("verify -d 3 --frequency big"))
+        );
+
+        let expected = Command::Solve(SolveOptions {
+            day: 1,
+            part: 2,
+            input: None,
+            frequency: Some(FrequencyType::Big),
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args("solve -d 1 -p 2 --frequency big"))
+        );
+    }
+
+    #[test]
+    fn should_parse_bench() {
+        let expected = Command::Bench(BenchOptions {
+            selection: Selection::All,
+            part: None,
+            iterations: 10,
+            frequency: None,
+        });
+
+        assert_eq!(Ok(expected), parse(&args("bench")));
+
+        let expected = Command::Bench(BenchOptions {
+            selection: Selection::Day(5),
+            part: Some(2),
+            iterations: 3,
+            frequency: None,
+        });
+
+        assert_eq!(Ok(expected), parse(&args("bench -d 5 -p 2 -n 3")));
+    }
+
+    #[test]
+    fn should_parse_verify() {
+        let expected = Command::Verify(VerifyOptions {
+            selection: Selection::All,
+            answers: "answers.toml".to_string(),
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("verify")));
+
+        let expected = Command::Verify(VerifyOptions {
+            selection: Selection::Day(3),
+            answers: "mine.toml".to_string(),
+            jobs: Some(2),
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args("verify --day 3 --answers mine.toml --jobs 2"))
+        );
+    }
+
+    #[test]
+    fn should_parse_limits() {
+        let expected = Command::Run(RunOptions {
+            selection: Selection::Day(1),
+            part: None,
+            input: None,
+            time: false,
+            format: Format::Text,
+            jobs: None,
+            frequency: None,
+            limits: LimitOptions {
+                timeout: Some(Duration::from_millis(1500)),
+                isolate: true,
+                memory_mb: Some(512),
+            },
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args("run -d 1 --timeout 1.5 --memory-limit 512"))
+        );
+    }
+
+    #[test]
+    fn should_reject_durations_out_of_range() {
+        assert_eq!(
+            Err("Invalid value for --timeout: 1e30".to_string()),
+            parse(&args("run --day 1 --timeout 1e30"))
+        );
+        assert_eq!(
+            Err("Invalid value for --interval: 1e30".to_string()),
+            parse(&args("watch --day 1 --interval 1e30"))
+        );
+    }
+
+    #[test]
+    fn should_parse_solve() {
+        let expected = Command::Solve(SolveOptions {
+            day: 2,
+            part: 1,
+            input: Some("in.txt".to_string()),
+            frequency: None,
+        });
+
+        assert_eq!(Ok(expected), parse(&args("solve -d 2 -p 1 -i in.txt")));
+    }
+
+    #[test]
+    fn should_parse_fetch() {
+        let expected = Command::Fetch(FetchOptions {
+            day: 3,
+            config: "aoc.toml".to_string(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("fetch -d 3")));
+
+        let expected = Command::Fetch(FetchOptions {
+            day: 3,
+            config: "ci.toml".to_string(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("fetch --day 3 --config ci.toml")));
+    }
+
+    #[test]
+    fn should_parse_submit() {
+        let expected = Command::Submit(SubmitOptions {
+            day: 4,
+            part: 2,
+            config: "aoc.toml".to_string(),
+            history: "submissions.log".to_string(),
+            force: false,
+            frequency: None,
+        });
+
+        assert_eq!(Ok(expected), parse(&args("submit -d 4 -p 2")));
+
+        let expected = Command::Submit(SubmitOptions {
+            day: 4,
+            part: 1,
+            config: "ci.toml".to_string(),
+            history: "tried.log".to_string(),
+            force: true,
+            frequency: None,
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args(
+                "submit -d 4 -p 1 --config ci.toml --history tried.log --force"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_parse_watch() {
+        let expected = Command::Watch(WatchOptions {
+            day: 5,
+            part: None,
+            interval: Duration::from_millis(500),
+            frequency: None,
+            limits: LimitOptions::default(),
+        });
+
+        assert_eq!(Ok(expected), parse(&args("watch -d 5")));
+
+        let expected = Command::Watch(WatchOptions {
+            day: 5,
+            part: Some(1),
+            interval: Duration::from_secs(2),
+            frequency: None,
+            limits: LimitOptions {
+                timeout: Some(Duration::from_secs(10)),
+                ..LimitOptions::default()
+            },
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args("watch -d 5 -p 1 --interval 2 --timeout 10"))
+        );
+    }
+
+    #[test]
+    fn should_parse_report() {
+        let expected = Command::Report(ReportOptions {
+            day: 1,
+            input: None,
+            trace: None,
+            follow: 1_000_000,
+            frequency: FrequencyType::I64,
+        });
+
+        assert_eq!(Ok(expected), parse(&args("report -d 1")));
+
+        let expected = Command::Report(ReportOptions {
+            day: 1,
+            input: Some("-".to_string()),
+            trace: Some("trace.csv".to_string()),
+            follow: 50,
+            frequency: FrequencyType::Big,
+        });
+
+        assert_eq!(
+            Ok(expected),
+            parse(&args(
+                "report -d 1 -i - --trace trace.csv --follow 50 --frequency big"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_parse_new_day() {
+        let expected = Command::NewDay(NewDayOptions {
+            day: 7,
+            title: None,
+        });
+
+        assert_eq!(Ok(expected), parse(&args("new-day 7")));
+
+        let expected = Command::NewDay(NewDayOptions {
+            day: 7,
+            title: Some("The Sum of Its Parts".to_string()),
+        });
+        let mut with_title = args("new-day --title");
+        with_title.push("The Sum of Its Parts".to_string());
+        with_title.push("7".to_string());
+
+        assert_eq!(Ok(expected), parse(&with_title));
+    }
+
+    #[test]
+    fn should_parse_list() {
+        assert_eq!(Ok(Command::List), parse(&args("list")));
+    }
+
+    #[test]
+    fn should_reject_invalid_arguments() {
+        assert!(parse(&args("run")).is_err());
+        assert!(parse(&args("run --all --day 1")).is_err());
+        assert!(parse(&args("run --all --input x")).is_err());
+        assert!(parse(&args("run --day 1 --part 3")).is_err());
+        assert!(parse(&args("run --day one")).is_err());
+        assert!(parse(&args("run --day")).is_err());
+        assert!(parse(&args("verify --all")).is_err());
+        assert!(parse(&args("bench --iterations 0")).is_err());
+        assert!(parse(&args("run --all --format xml")).is_err());
+        assert!(parse(&args("run --all --jobs 0")).is_err());
+        assert!(parse(&args("run --all --timeout -1")).is_err());
+        assert!(parse(&args("run --all --timeout inf")).is_err());
+        assert!(parse(&args("run -d 1 -i - --isolate")).is_err());
+        assert!(parse(&args("solve --day 1")).is_err());
+        assert!(parse(&args("fetch")).is_err());
+        assert!(parse(&args("fetch -d 0")).is_err());
+        assert!(parse(&args("submit -d 4")).is_err());
+        assert!(parse(&args("watch")).is_err());
+        assert!(parse(&args("watch -d 5 --interval 0")).is_err());
+        assert!(parse(&args("report")).is_err());
+        assert!(parse(&args("report -d 1 --follow 0")).is_err());
+        assert!(parse(&args("new-day")).is_err());
+        assert!(parse(&args("new-day 0")).is_err());
+        assert!(parse(&args("new-day 7 8")).is_err());
+        assert!(parse(&args("fly")).is_err());
+    }
+}
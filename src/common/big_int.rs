@@ -0,0 +1,369 @@
+use super::{AocError, Result};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// A signed integer of any size. Only the operations the puzzles need are supported, and none of
+/// them are fast, but none of them can overflow either.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct BigInt {
+    negative: bool,
+    /// Base 2^32 digits, least significant first, with no trailing zeros. Zero has no digits.
+    digits: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn new(negative: bool, mut digits: Vec<u32>) -> BigInt {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        BigInt {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+
+    /// Euclidean division, so the remainder is never negative. `None` when dividing by zero.
+    pub fn div_rem_euclid(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (quotient, remainder) = div_rem_magnitude(&self.digits, &divisor.digits);
+        let quotient = BigInt::new(false, quotient);
+        let remainder = BigInt::new(false, remainder);
+        if !self.negative {
+            return Some((quotient.with_sign(divisor.negative), remainder));
+        }
+
+        // Rounding the quotient away from zero leaves a positive remainder of |divisor| - r.
+        if remainder.is_zero() {
+            Some((quotient.with_sign(!divisor.negative), remainder))
+        } else {
+            let quotient = &quotient + &BigInt::from(1);
+            let remainder = &divisor.abs() - &remainder;
+            Some((quotient.with_sign(!divisor.negative), remainder))
+        }
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::new(false, self.digits.clone())
+    }
+
+    fn with_sign(self, negative: bool) -> BigInt {
+        BigInt::new(negative, self.digits)
+    }
+
+    /// The value as an `i64`, if it fits.
+    pub fn to_i64(&self) -> Option<i64> {
+        let magnitude = match self.digits.as_slice() {
+            [] => 0,
+            [low] => u64::from(*low),
+            [low, high] => u64::from(*low) | u64::from(*high) << 32,
+            _ => return None,
+        };
+
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+    for i in 0..a.len().max(b.len()) {
+        let total = u64::from(*a.get(i).unwrap_or(&0)) + u64::from(*b.get(i).unwrap_or(&0)) + carry;
+        sum.push(total as u32);
+        carry = total >> 32;
+    }
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    sum
+}
+
+/// `a - b`, where `a` is at least as large as `b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, digit) in a.iter().enumerate() {
+        let mut total = i64::from(*digit) - i64::from(*b.get(i).unwrap_or(&0)) - borrow;
+        borrow = 0;
+        if total < 0 {
+            total += 1 << 32;
+            borrow = 1;
+        }
+        difference.push(total as u32);
+    }
+    difference
+}
+
+/// Long division one bit at a time. Slow, but simple, and the puzzles only divide rarely.
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+
+    for bit in (0..a.len() * 32).rev() {
+        // Shift the remainder left by one, bringing in the next bit of `a`.
+        let mut carry = (a[bit / 32] >> (bit % 32)) & 1;
+        for digit in remainder.iter_mut() {
+            let next = *digit >> 31;
+            *digit = *digit << 1 | carry;
+            carry = next;
+        }
+        if carry > 0 {
+            remainder.push(carry);
+        }
+
+        if compare_magnitude(&remainder, b) != Ordering::Less {
+            remainder = sub_magnitude(&remainder, b);
+            while remainder.last() == Some(&0) {
+                remainder.pop();
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+
+    (quotient, remainder)
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> BigInt {
+        let magnitude = value.unsigned_abs();
+        BigInt::new(value < 0, vec![magnitude as u32, (magnitude >> 32) as u32])
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.digits, &other.digits),
+            (true, true) => compare_magnitude(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, add_magnitude(&self.digits, &other.digits));
+        }
+
+        match compare_magnitude(&self.digits, &other.digits) {
+            Ordering::Less => {
+                BigInt::new(other.negative, sub_magnitude(&other.digits, &self.digits))
+            }
+            _ => BigInt::new(self.negative, sub_magnitude(&self.digits, &other.digits)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::new(!self.negative, self.digits.clone())
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // Peel off nine decimal digits at a time, least significant first.
+        let mut chunks = Vec::new();
+        let mut digits = self.digits.clone();
+        while !digits.is_empty() {
+            let mut remainder = 0u64;
+            for digit in digits.iter_mut().rev() {
+                let value = remainder << 32 | u64::from(*digit);
+                *digit = (value / 1_000_000_000) as u32;
+                remainder = value % 1_000_000_000;
+            }
+            while digits.last() == Some(&0) {
+                digits.pop();
+            }
+            chunks.push(remainder);
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses decimal digits with an optional `+` or `-` sign.
+impl FromStr for BigInt {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<BigInt> {
+        let (negative, number) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(AocError::invalid(text, "Expected a number"));
+        }
+
+        let mut digits: Vec<u32> = Vec::new();
+        for b in number.bytes() {
+            // digits = digits * 10 + b
+            let mut carry = u64::from(b - b'0');
+            for digit in digits.iter_mut() {
+                let value = u64::from(*digit) * 10 + carry;
+                *digit = value as u32;
+                carry = value >> 32;
+            }
+            if carry > 0 {
+                digits.push(carry as u32);
+            }
+        }
+
+        Ok(BigInt::new(negative, digits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(text: &str) -> BigInt {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn should_parse_and_display() {
+        for text in &[
+            "0",
+            "7",
+            "-42",
+            "4294967296",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(*text, big(text).to_string());
+        }
+        assert_eq!("5", big("+5").to_string());
+        assert_eq!(big("0"), big("-0"));
+        assert!("".parse::<BigInt>().is_err());
+        assert!("+".parse::<BigInt>().is_err());
+        assert!("1x".parse::<BigInt>().is_err());
+    }
+
+    #[test]
+    fn should_add_and_subtract_past_i128() {
+        let max = big(&i128::MAX.to_string());
+
+        assert_eq!(
+            "170141183460469231731687303715884105728",
+            (&max + &big("1")).to_string()
+        );
+        assert_eq!(big("-3"), &big("2") - &big("5"));
+        assert_eq!(big("3"), &big("-2") + &big("5"));
+        assert_eq!(big("0"), &max - &max);
+        assert_eq!(big("-4294967297"), &big("-4294967296") + &big("-1"));
+    }
+
+    #[test]
+    fn should_order_by_value() {
+        let mut values = vec![
+            big("10"),
+            big("-20"),
+            big("-3"),
+            big("4294967296"),
+            big("0"),
+        ];
+        values.sort();
+
+        assert_eq!(
+            vec![
+                big("-20"),
+                big("-3"),
+                big("0"),
+                big("10"),
+                big("4294967296")
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn should_divide_euclidean() {
+        let cases = [(7, 2), (-7, 2), (7, -2), (-7, -2), (-8, 2), (0, 5)];
+        for (a, b) in cases.iter() {
+            let (q, r) = BigInt::from(*a).div_rem_euclid(&BigInt::from(*b)).unwrap();
+
+            assert_eq!(
+                (
+                    BigInt::from(a.div_euclid(*b)),
+                    BigInt::from(a.rem_euclid(*b))
+                ),
+                (q, r),
+                "{} / {}",
+                a,
+                b
+            );
+        }
+
+        let (q, r) = big("-100000000000000000000")
+            .div_rem_euclid(&big("30000000000"))
+            .unwrap();
+        assert_eq!((big("-3333333334"), big("20000000000")), (q, r));
+        assert_eq!(None, big("1").div_rem_euclid(&big("0")));
+    }
+
+    #[test]
+    fn should_convert_to_i64_when_it_fits() {
+        assert_eq!(Some(i64::MIN), BigInt::from(i64::MIN).to_i64());
+        assert_eq!(Some(-5), big("-5").to_i64());
+        assert_eq!(None, big("9223372036854775808").to_i64());
+    }
+}
@@ -0,0 +1,221 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+#[derive(Debug)]
+pub enum AocError {
+    /// A line of the puzzle input could not be understood.
+    InvalidInput {
+        file: Option<String>,
+        line: Option<usize>,
+        column: Option<usize>,
+        text: String,
+        reason: String,
+    },
+    /// The puzzle input could not be read.
+    Io { file: String, source: io::Error },
+    /// The puzzle input was read, but has nothing in it.
+    EmptyInput { file: String },
+    /// The input was valid, but has no answer.
+    NoSolution(String),
+    /// The solver panicked, with the panic's message.
+    Panicked(String),
+    /// The solver was still running when its time ran out.
+    TimedOut(Duration),
+    /// The solver's process ran out of memory under the given limit, in megabytes.
+    MemoryExceeded(u64),
+    /// The solver's process failed, with whatever it printed about why.
+    Child(String),
+    /// A request to the puzzle website failed.
+    Http { url: String, reason: String },
+    /// Something needed from the configuration is missing or invalid.
+    Config(String),
+}
+
+impl AocError {
+    pub fn invalid(text: &str, reason: &str) -> AocError {
+        AocError::InvalidInput {
+            file: None,
+            line: None,
+            column: None,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn no_solution(reason: &str) -> AocError {
+        AocError::NoSolution(reason.to_string())
+    }
+
+    /// Records the (1-indexed) line the error came from, if it isn't already known.
+    pub fn at_line(self, number: usize) -> AocError {
+        match self {
+            AocError::InvalidInput {
+                file,
+                line: None,
+                column,
+                text,
+                reason,
+            } => AocError::InvalidInput {
+                file,
+                line: Some(number),
+                column,
+                text,
+                reason,
+            },
+            other => other,
+        }
+    }
+
+    /// Records the (1-indexed) column within the line the error came from, if it isn't already known.
+    pub fn at_column(self, number: usize) -> AocError {
+        match self {
+            AocError::InvalidInput {
+                file,
+                line,
+                column: None,
+                text,
+                reason,
+            } => AocError::InvalidInput {
+                file,
+                line,
+                column: Some(number),
+                text,
+                reason,
+            },
+            other => other,
+        }
+    }
+
+    /// Records the file the error came from, if it isn't already known.
+    pub fn in_file(self, path: &str) -> AocError {
+        match self {
+            AocError::InvalidInput {
+                file: None,
+                line,
+                column,
+                text,
+                reason,
+            } => AocError::InvalidInput {
+                file: Some(path.to_string()),
+                line,
+                column,
+                text,
+                reason,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::InvalidInput {
+                file,
+                line,
+                column,
+                text,
+                reason,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file)?;
+                }
+                if let Some(line) = line {
+                    write!(f, "{}:", line)?;
+                }
+                if let Some(column) = column {
+                    write!(f, "{}:", column)?;
+                }
+                if file.is_some() || line.is_some() || column.is_some() {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}: {:?}", reason, text)
+            }
+            AocError::Io { file, source } if source.kind() == io::ErrorKind::NotFound => {
+                write!(f, "Input file {} does not exist", file)
+            }
+            AocError::Io { file, source } => write!(f, "Could not read {}: {}", file, source),
+            AocError::EmptyInput { file } => {
+                write!(f, "Input file {} is empty or only whitespace", file)
+            }
+            AocError::NoSolution(reason) => write!(f, "No solution: {}", reason),
+            AocError::Panicked(message) => write!(f, "Solver panicked: {}", message),
+            AocError::TimedOut(limit) => write!(f, "Timed out after {:.2?}", limit),
+            AocError::MemoryExceeded(limit) => {
+                write!(f, "Ran out of memory under the {}MB limit", limit)
+            }
+            AocError::Child(message) => write!(f, "{}", message),
+            AocError::Http { url, reason } => write!(f, "Request to {} failed: {}", url, reason),
+            AocError::Config(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_describe_invalid_input() {
+        let error = AocError::invalid("+x", "Not a number")
+            .at_line(3)
+            .in_file("input.txt");
+
+        assert_eq!("input.txt:3: Not a number: \"+x\"", error.to_string());
+    }
+
+    #[test]
+    fn should_describe_column() {
+        let error = AocError::invalid("+x", "Not a number")
+            .at_column(5)
+            .at_line(3)
+            .at_column(7)
+            .in_file("input.txt");
+
+        assert_eq!("input.txt:3:5: Not a number: \"+x\"", error.to_string());
+    }
+
+    #[test]
+    fn should_keep_first_location() {
+        let error = AocError::invalid("+x", "Not a number")
+            .at_line(3)
+            .at_line(5)
+            .in_file("a.txt")
+            .in_file("b.txt");
+
+        assert_eq!("a.txt:3: Not a number: \"+x\"", error.to_string());
+    }
+
+    #[test]
+    fn should_describe_io_error() {
+        let error = AocError::Io {
+            file: "missing.txt".to_string(),
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+
+        assert_eq!("Input file missing.txt does not exist", error.to_string());
+
+        let error = AocError::Io {
+            file: "locked.txt".to_string(),
+            source: io::Error::new(io::ErrorKind::PermissionDenied, "permission denied"),
+        };
+
+        assert_eq!(
+            "Could not read locked.txt: permission denied",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,407 @@
+use super::{Grid, Point2};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Anything whose nodes lead to other nodes. Edges are one way; an undirected graph lists each
+/// edge from both ends.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes reachable in one step, each with the cost of taking that step.
+    fn edges(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+/// A grid of open (`true`) and blocked (`false`) cells, where each step is to an open orthogonal
+/// neighbour and costs 1.
+impl Graph for Grid<bool> {
+    type Node = Point2;
+
+    fn edges(&self, node: &Point2) -> Vec<(Point2, u64)> {
+        self.neighbours_4(*node)
+            .filter(|p| self[*p])
+            .map(|p| (p, 1))
+            .collect()
+    }
+}
+
+/// A graph defined by a function from each node to its edges, for graphs too big or too
+/// irregular to store.
+pub struct FromFn<N, F> {
+    edges: F,
+    node: PhantomData<N>,
+}
+
+pub fn from_fn<N, F>(edges: F) -> FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    FromFn {
+        edges,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn edges(&self, node: &N) -> Vec<(N, u64)> {
+        (self.edges)(node)
+    }
+}
+
+/// The cheapest way found from one node to another, including both ends.
+#[derive(PartialEq, Debug)]
+pub struct Path<N> {
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+/// The number of steps to every node reachable from `start`, ignoring edge costs.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> HashMap<G::Node, usize> {
+    let mut steps = HashMap::new();
+    let mut queue = VecDeque::new();
+    steps.insert(start.clone(), 0);
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        let next_steps = steps[&node] + 1;
+        for (next, _) in graph.edges(&node) {
+            if !steps.contains_key(&next) {
+                steps.insert(next.clone(), next_steps);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    steps
+}
+
+/// The cheapest path from `start` to the first node that `is_goal` accepts.
+pub fn dijkstra<G, F>(graph: &G, start: G::Node, is_goal: F) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    F: Fn(&G::Node) -> bool,
+{
+    search(graph, start, is_goal, |_| 0)
+}
+
+/// The cheapest path from `start` to `goal`, guided by a heuristic. The heuristic must never
+/// overestimate the remaining cost, or the path found may not be the cheapest.
+pub fn a_star<G, H>(
+    graph: &G,
+    start: G::Node,
+    goal: &G::Node,
+    heuristic: H,
+) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    H: Fn(&G::Node) -> u64,
+{
+    search(graph, start, |node| node == goal, heuristic)
+}
+
+fn search<G, F, H>(graph: &G, start: G::Node, is_goal: F, heuristic: H) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    F: Fn(&G::Node) -> bool,
+    H: Fn(&G::Node) -> u64,
+{
+    // Nodes are kept in a list and referred to by index, so they don't need to be ordered.
+    let mut nodes = vec![start.clone()];
+    let mut index = HashMap::new();
+    let mut cost = vec![0];
+    let mut previous: Vec<Option<usize>> = vec![None];
+    let mut queue = BinaryHeap::new();
+    index.insert(start.clone(), 0);
+    queue.push(Reverse((heuristic(&start), 0, 0)));
+
+    while let Some(Reverse((_, cost_so_far, current))) = queue.pop() {
+        if cost_so_far > cost[current] {
+            continue;
+        }
+
+        if is_goal(&nodes[current]) {
+            let mut path = vec![nodes[current].clone()];
+            let mut at = current;
+            while let Some(before) = previous[at] {
+                path.push(nodes[before].clone());
+                at = before;
+            }
+            path.reverse();
+
+            return Some(Path {
+                nodes: path,
+                cost: cost_so_far,
+            });
+        }
+
+        for (next, step) in graph.edges(&nodes[current]) {
+            let next_cost = cost_so_far + step;
+            let i = match index.get(&next) {
+                Some(&i) if next_cost >= cost[i] => continue,
+                Some(&i) => i,
+                None => {
+                    nodes.push(next.clone());
+                    cost.push(next_cost);
+                    previous.push(None);
+                    index.insert(next.clone(), nodes.len() - 1);
+                    nodes.len() - 1
+                }
+            };
+
+            cost[i] = next_cost;
+            previous[i] = Some(current);
+            queue.push(Reverse((next_cost + heuristic(&next), next_cost, i)));
+        }
+    }
+
+    None
+}
+
+/// Orders the nodes so that each comes after every node with an edge to it. When several nodes
+/// are ready at once, the smallest by `compare` goes first. `None` if the edges form a cycle.
+/// Nodes only reached through edges are included even if they aren't listed.
+pub fn topological_sort<G, F>(graph: &G, nodes: &[G::Node], compare: F) -> Option<Vec<G::Node>>
+where
+    G: Graph,
+    F: Fn(&G::Node, &G::Node) -> Ordering,
+{
+    let mut incoming: HashMap<G::Node, usize> = HashMap::new();
+    let mut edges = HashMap::new();
+    let mut pending: VecDeque<G::Node> = nodes.iter().cloned().collect();
+
+    while let Some(node) = pending.pop_front() {
+        if edges.contains_key(&node) {
+            continue;
+        }
+        incoming.entry(node.clone()).or_insert(0);
+
+        let next: Vec<G::Node> = graph.edges(&node).into_iter().map(|(n, _)| n).collect();
+        for n in next.iter() {
+            *incoming.entry(n.clone()).or_insert(0) += 1;
+            pending.push_back(n.clone());
+        }
+        edges.insert(node, next);
+    }
+
+    let mut ready: Vec<G::Node> = incoming
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(node, _)| node.clone())
+        .collect();
+    let mut order = Vec::new();
+
+    while let Some(first) = (0..ready.len()).min_by(|a, b| compare(&ready[*a], &ready[*b])) {
+        let node = ready.swap_remove(first);
+        for next in edges[&node].iter() {
+            let count = incoming.get_mut(next).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                ready.push(next.clone());
+            }
+        }
+        order.push(node);
+    }
+
+    if order.len() == incoming.len() {
+        Some(order)
+    } else {
+        None
+    }
+}
+
+/// Disjoint sets of the numbers `0..size`, for grouping things into connected components.
+#[derive(Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    pub fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..size).collect(),
+            size: vec![1; size],
+            sets: size,
+        }
+    }
+
+    /// The representative of the set containing `item`, which is the same for every member.
+    pub fn find(&mut self, item: usize) -> usize {
+        let mut root = item;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut at = item;
+        while self.parent[at] != root {
+            let next = self.parent[at];
+            self.parent[at] = root;
+            at = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns whether they were separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (small, large) = if self.size[a] < self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.sets -= 1;
+        true
+    }
+
+    /// How many separate sets there are.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+/// Groups the nodes into sets that are connected by edges in either direction. Edges to nodes
+/// that aren't listed are ignored.
+pub fn components<G: Graph>(graph: &G, nodes: &[G::Node]) -> Vec<Vec<G::Node>> {
+    let index: HashMap<&G::Node, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let mut sets = UnionFind::new(nodes.len());
+
+    for (i, node) in nodes.iter().enumerate() {
+        for (next, _) in graph.edges(node) {
+            if let Some(&j) = index.get(&next) {
+                sets.union(i, j);
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<G::Node>> = HashMap::new();
+    for (i, node) in nodes.iter().enumerate() {
+        groups.entry(sets.find(i)).or_default().push(node.clone());
+    }
+
+    let mut groups: Vec<Vec<G::Node>> = groups.into_values().collect();
+    // Keep the result stable, in the order each group's first node was listed.
+    groups.sort_by_key(|group| index[&group[0]]);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i32, y: i32) -> Point2 {
+        Point2::new(x, y)
+    }
+
+    fn maze() -> Grid<bool> {
+        // . . . .
+        // # # # .
+        // . . . .
+        // . # # #
+        let walls = [p(0, 1), p(1, 1), p(2, 1), p(1, 3), p(2, 3), p(3, 3)];
+        Grid::from_fn(p(0, 0), 4, 4, |point| !walls.contains(&point))
+    }
+
+    /// A small directed graph, where each letter leads to the letters listed after it.
+    fn letters(edges: &'static [(char, &'static str)]) -> impl Graph<Node = char> {
+        from_fn(move |node: &char| {
+            edges
+                .iter()
+                .filter(|(from, _)| from == node)
+                .flat_map(|(_, to)| to.chars().map(|c| (c, 1)))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn should_count_steps_by_bfs() {
+        let steps = bfs(&maze(), p(0, 0));
+
+        assert_eq!(Some(&3), steps.get(&p(3, 0)));
+        assert_eq!(Some(&9), steps.get(&p(0, 3)));
+        assert_eq!(None, steps.get(&p(1, 1)));
+        assert_eq!(10, steps.len());
+    }
+
+    #[test]
+    fn should_find_cheapest_path() {
+        // Going the long way round, a -> b -> c -> d, is cheaper than the direct edge.
+        let graph = from_fn(|node: &char| match node {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        });
+        let path = dijkstra(&graph, 'a', |n| *n == 'd').unwrap();
+
+        assert_eq!(vec!['a', 'b', 'c', 'd'], path.nodes);
+        assert_eq!(6, path.cost);
+        assert_eq!(None, dijkstra(&graph, 'b', |n| *n == 'a'));
+    }
+
+    #[test]
+    fn should_find_path_with_heuristic() {
+        let grid = maze();
+        let goal = p(0, 3);
+        let path = a_star(&grid, p(0, 0), &goal, |n| n.manhattan(goal) as u64).unwrap();
+
+        assert_eq!(9, path.cost);
+        assert_eq!(10, path.nodes.len());
+        assert_eq!(Some(&p(0, 0)), path.nodes.first());
+        assert_eq!(Some(&goal), path.nodes.last());
+        assert_eq!(None, a_star(&grid, p(0, 0), &p(1, 1), |_| 0));
+    }
+
+    #[test]
+    fn should_sort_topologically_with_tie_breaker() {
+        let steps = letters(&[('C', "AF"), ('A', "BD"), ('B', "E"), ('D', "E"), ('F', "E")]);
+        let order = topological_sort(&steps, &['C'], |a, b| a.cmp(b)).unwrap();
+        let reversed = topological_sort(&steps, &['C'], |a, b| b.cmp(a)).unwrap();
+
+        assert_eq!("CABDFE", order.iter().collect::<String>());
+        assert_eq!("CFADBE", reversed.iter().collect::<String>());
+    }
+
+    #[test]
+    fn should_refuse_to_sort_cycle() {
+        let steps = letters(&[('A', "B"), ('B', "C"), ('C', "A"), ('D', "A")]);
+
+        assert_eq!(None, topological_sort(&steps, &['D'], |a, b| a.cmp(b)));
+    }
+
+    #[test]
+    fn should_merge_sets() {
+        let mut sets = UnionFind::new(5);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert_eq!(2, sets.sets());
+        assert_eq!(sets.find(0), sets.find(4));
+        assert_ne!(sets.find(0), sets.find(2));
+    }
+
+    #[test]
+    fn should_group_connected_components() {
+        let graph = letters(&[('a', "b"), ('c', "b"), ('d', "e"), ('e', "z")]);
+        let groups = components(&graph, &['a', 'b', 'c', 'd', 'e', 'f']);
+
+        assert_eq!(vec![vec!['a', 'b', 'c'], vec!['d', 'e'], vec!['f']], groups);
+    }
+}
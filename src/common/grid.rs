@@ -0,0 +1,363 @@
+use super::Point2;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::ops::{Index, IndexMut};
+
+const ORTHOGONAL: [Point2; 4] = [
+    Point2::new(0, -1),
+    Point2::new(-1, 0),
+    Point2::new(1, 0),
+    Point2::new(0, 1),
+];
+const SURROUNDING: [Point2; 8] = [
+    Point2::new(-1, -1),
+    Point2::new(0, -1),
+    Point2::new(1, -1),
+    Point2::new(-1, 0),
+    Point2::new(1, 0),
+    Point2::new(-1, 1),
+    Point2::new(0, 1),
+    Point2::new(1, 1),
+];
+
+fn point_at(origin: Point2, width: usize, index: usize) -> Point2 {
+    Point2::new(
+        origin.x + (index % width) as i32,
+        origin.y + (index / width) as i32,
+    )
+}
+
+/// A dense rectangle of cells, addressed by points that start from an arbitrary, possibly negative, origin.
+/// Cells are stored row by row, so rows are slices but columns have to be gathered.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Grid<T> {
+    origin: Point2,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid whose top-left cell is at `origin`, with every cell set to `fill`.
+    pub fn new(origin: Point2, width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            origin,
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// The smallest grid that includes both corners. Empty if `max` is above or left of `min`.
+    pub fn covering(min: Point2, max: Point2, fill: T) -> Grid<T> {
+        let length = |from: i32, to: i32| (i64::from(to) - i64::from(from) + 1).max(0) as usize;
+        Grid::new(min, length(min.x, max.x), length(min.y, max.y), fill)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid by calling `f` with each point, row by row.
+    pub fn from_fn<F: FnMut(Point2) -> T>(
+        origin: Point2,
+        width: usize,
+        height: usize,
+        f: F,
+    ) -> Grid<T> {
+        Grid {
+            origin,
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|i| point_at(origin, width, i))
+                .map(f)
+                .collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The top-left point.
+    pub fn min(&self) -> Point2 {
+        self.origin
+    }
+
+    /// The bottom-right point. Only meaningful if the grid isn't empty.
+    pub fn max(&self) -> Point2 {
+        Point2::new(
+            self.origin.x + self.width as i32 - 1,
+            self.origin.y + self.height as i32 - 1,
+        )
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        self.offset_of(point).is_some()
+    }
+
+    /// Whether the point is in the outermost ring of cells.
+    pub fn is_edge(&self, point: Point2) -> bool {
+        let (min, max) = (self.min(), self.max());
+        self.contains(point)
+            && (point.x == min.x || point.x == max.x || point.y == min.y || point.y == max.y)
+    }
+
+    fn offset_of(&self, point: Point2) -> Option<usize> {
+        let x = usize::try_from(i64::from(point.x) - i64::from(self.origin.x)).ok()?;
+        let y = usize::try_from(i64::from(point.y) - i64::from(self.origin.y)).ok()?;
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    fn point(&self, index: usize) -> Point2 {
+        point_at(self.origin, self.width, index)
+    }
+
+    pub fn get(&self, point: Point2) -> Option<&T> {
+        self.offset_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.offset_of(point).map(move |i| &mut self.cells[i])
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2> + '_ {
+        (0..self.width * self.height).map(move |i| self.point(i))
+    }
+
+    /// Every cell along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (self.point(i), cell))
+    }
+
+    /// Every cell, row by row.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The cells with the given y, from left to right.
+    pub fn row(&self, y: i32) -> Option<&[T]> {
+        let start = self.offset_of(Point2::new(self.origin.x, y))?;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    /// Each row, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a size of 0, and a grid with no width has no cells to show anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells with the given x, from top to bottom. Empty if x is outside the grid.
+    pub fn column(&self, x: i32) -> impl Iterator<Item = &T> {
+        let start = self.offset_of(Point2::new(x, self.origin.y));
+        let step = self.width.max(1);
+        let cells = match start {
+            Some(start) => &self.cells[start..],
+            None => &[],
+        };
+        cells.iter().step_by(step)
+    }
+
+    /// Each column, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let min = self.origin.x;
+        (min..min + self.width as i32).map(move |x| self.column(x))
+    }
+
+    /// The up to 4 points above, left, right and below, that are inside the grid.
+    pub fn neighbours_4(&self, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        self.offsets(point, &ORTHOGONAL)
+    }
+
+    /// The up to 8 points surrounding, including diagonals, that are inside the grid.
+    pub fn neighbours_8(&self, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        self.offsets(point, &SURROUNDING)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        point: Point2,
+        offsets: &'a [Point2],
+    ) -> impl Iterator<Item = Point2> + 'a {
+        offsets
+            .iter()
+            .map(move |offset| point + *offset)
+            .filter(move |p| self.contains(*p))
+    }
+
+    /// Every point reachable from `start` through orthogonal neighbours for which `passable` is true,
+    /// in the order they are reached. Empty if `start` itself isn't passable.
+    pub fn flood_fill<F: Fn(Point2, &T) -> bool>(&self, start: Point2, passable: F) -> Vec<Point2> {
+        let can_enter = |p: Point2| self.get(p).is_some_and(|cell| passable(p, cell));
+        if !can_enter(start) {
+            return Vec::new();
+        }
+
+        let mut seen = vec![false; self.cells.len()];
+        let mut queue = VecDeque::from(vec![start]);
+        let mut filled = Vec::new();
+        seen[self.offset_of(start).unwrap()] = true;
+
+        while let Some(point) = queue.pop_front() {
+            filled.push(point);
+            for next in self.neighbours_4(point) {
+                let i = self.offset_of(next).unwrap();
+                if !seen[i] && can_enter(next) {
+                    seen[i] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// A grid of the same shape, with `f` applied to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            origin: self.origin,
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the grid", point),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut T {
+        match self.offset_of(point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{} is outside the grid", point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i32, y: i32) -> Point2 {
+        Point2::new(x, y)
+    }
+
+    fn numbered() -> Grid<i32> {
+        // 0 1 2
+        // 3 4 5
+        Grid::from_fn(p(-1, -1), 3, 2, |point| (point.y + 1) * 3 + point.x + 1)
+    }
+
+    #[test]
+    fn should_address_cells_from_origin() {
+        let mut grid = numbered();
+
+        assert_eq!(p(-1, -1), grid.min());
+        assert_eq!(p(1, 0), grid.max());
+        assert_eq!(0, grid[p(-1, -1)]);
+        assert_eq!(5, grid[p(1, 0)]);
+        assert_eq!(None, grid.get(p(2, 0)));
+        assert_eq!(None, grid.get(p(-2, 0)));
+
+        grid[p(0, 0)] = 40;
+
+        assert_eq!(Some(&40), grid.get(p(0, 0)));
+    }
+
+    #[test]
+    fn should_cover_corners() {
+        let grid = Grid::covering(p(-2, 3), p(2, 4), '.');
+
+        assert_eq!((5, 2), (grid.width(), grid.height()));
+        assert!(grid.contains(p(-2, 3)) && grid.contains(p(2, 4)));
+        assert_eq!(0, Grid::covering(p(1, 1), p(0, 0), '.').values().count());
+    }
+
+    #[test]
+    fn should_find_edges() {
+        let grid = Grid::covering(p(0, 0), p(2, 2), 0);
+
+        assert!(grid.is_edge(p(0, 1)));
+        assert!(grid.is_edge(p(2, 2)));
+        assert!(!grid.is_edge(p(1, 1)));
+        assert!(!grid.is_edge(p(3, 1)));
+    }
+
+    #[test]
+    fn should_iterate_rows_and_columns() {
+        let grid = numbered();
+
+        assert_eq!(
+            vec![&[0, 1, 2][..], &[3, 4, 5][..]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&[3, 4, 5][..]), grid.row(0));
+        assert_eq!(vec![&1, &4], grid.column(0).collect::<Vec<_>>());
+        assert_eq!(0, grid.column(5).count());
+        assert_eq!(
+            vec![vec![0, 3], vec![1, 4], vec![2, 5]],
+            grid.columns()
+                .map(|c| c.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(p(-1, -1), &0), (p(0, -1), &1)],
+            grid.iter().take(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_find_neighbours_inside_grid() {
+        let grid = numbered();
+
+        assert_eq!(
+            vec![p(0, -1), p(-1, 0)],
+            grid.neighbours_4(p(-1, -1)).collect::<Vec<_>>()
+        );
+        assert_eq!(3, grid.neighbours_8(p(-1, -1)).count());
+        assert_eq!(5, grid.neighbours_8(p(0, 0)).count());
+    }
+
+    #[test]
+    fn should_flood_fill() {
+        // # . #
+        // . . #
+        // # # .
+        let walls = [p(0, 0), p(2, 0), p(2, 1), p(0, 2), p(1, 2)];
+        let grid = Grid::from_fn(p(0, 0), 3, 3, |point| walls.contains(&point));
+        let mut filled = grid.flood_fill(p(1, 1), |_, wall| !wall);
+        filled.sort();
+
+        assert_eq!(vec![p(0, 1), p(1, 0), p(1, 1)], filled);
+        assert!(grid.flood_fill(p(0, 0), |_, wall| !wall).is_empty());
+    }
+
+    #[test]
+    fn should_map_cells() {
+        let grid = numbered().map(|v| v % 2 == 0);
+
+        assert_eq!(p(-1, -1), grid.min());
+        assert_eq!(Some(&true), grid.get(p(-1, -1)));
+        assert_eq!(Some(&false), grid.get(p(0, -1)));
+    }
+}
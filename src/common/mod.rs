@@ -1,13 +1,70 @@
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
+mod big_int;
+mod error;
+pub mod graph;
+mod grid;
+mod parse;
+mod point;
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+pub use big_int::BigInt;
+pub use error::{AocError, Result};
+pub use grid::Grid;
+pub use parse::{blocks, char_grid, integer, integers, Fields, Pattern};
+pub use point::{Point2, Point3, Point4};
+
+/// Reads every line of the puzzle input, where a path of `-` means stdin.
+/// Input with nothing but whitespace in it is treated as an error, since no puzzle has an empty input.
+pub fn read_input(path: &str) -> Result<Vec<String>> {
+    let (file, lines) = if path == "-" {
+        let file = "<stdin>".to_string();
+        let lines = io::stdin()
+            .lock()
+            .lines()
+            .collect::<io::Result<Vec<String>>>()
+            .map_err(|source| AocError::Io {
+                file: file.clone(),
+                source,
+            })?;
+        (file, lines)
+    } else {
+        (path.to_string(), read_lines(path)?)
+    };
+
+    if lines.iter().all(|l| l.trim().is_empty()) {
+        return Err(AocError::EmptyInput { file });
+    }
+
+    Ok(lines)
+}
+
+pub fn read_lines<P>(filename: P) -> Result<Vec<String>>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    let to_error = |source| AocError::Io {
+        file: filename.as_ref().display().to_string(),
+        source,
+    };
+
+    let file = File::open(&filename).map_err(to_error)?;
+    io::BufReader::new(file)
+        .lines()
+        .collect::<io::Result<_>>()
+        .map_err(to_error)
+}
+
+/// Parses each line in turn, recording the line number of the first one that fails.
+pub fn parse_lines<T, F>(lines: &[String], parse: F) -> Result<Vec<T>>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| parse(l).map_err(|e| e.at_line(i + 1)))
+        .collect()
 }
 
 #[cfg(test)]
@@ -23,16 +80,57 @@ mod tests {
             String::from("line 3"),
         ];
 
-        if let Ok(lines) = read_lines(filename) {
-            let result: Vec<String> = lines.map(|l| l.unwrap()).collect();
-            assert_eq!(expected, result);
+        assert_eq!(expected, read_lines(filename).unwrap());
+    }
+
+    #[test]
+    fn should_read_input_from_path() {
+        let result = read_input("./inputs/test/test.txt").unwrap();
+
+        assert_eq!(vec!["line 1", "line 2", "line 3"], result);
+    }
+
+    #[test]
+    fn should_reject_empty_input() {
+        for filename in &["./inputs/test/empty.txt", "./inputs/test/whitespace.txt"] {
+            match read_input(filename) {
+                Err(AocError::EmptyInput { file }) => assert_eq!(*filename, file),
+                other => panic!("Expected an empty input error, got {:?}", other),
+            }
         }
     }
 
+    #[test]
+    fn should_reject_unreadable_input() {
+        assert!(read_input("./inputs/test/non-existent.txt").is_err());
+        assert!(read_input("./inputs/test").is_err());
+    }
+
     #[test]
     fn should_return_none() {
         let filename = "./inputs/test/non-existent.txt";
 
         assert!(read_lines(filename).is_err(), "File should not be read");
     }
+
+    #[test]
+    fn should_name_missing_file() {
+        let filename = "./inputs/test/non-existent.txt";
+
+        match read_lines(filename) {
+            Err(AocError::Io { file, .. }) => assert_eq!(filename, file),
+            other => panic!("Expected an IO error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_report_failing_line() {
+        let lines = vec![String::from("1"), String::from("x"), String::from("3")];
+        let result = parse_lines(&lines, |l| {
+            l.parse::<u32>()
+                .map_err(|_| AocError::invalid(l, "Not a number"))
+        });
+
+        assert_eq!("2: Not a number: \"x\"", result.unwrap_err().to_string());
+    }
 }
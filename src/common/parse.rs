@@ -0,0 +1,268 @@
+use super::{AocError, Grid, Point2, Result};
+use std::str::FromStr;
+
+/// Parses text that should be a single integer, with an optional sign and surrounding whitespace.
+pub fn integer<T: FromStr>(text: &str) -> Result<T> {
+    text.trim()
+        .parse()
+        .map_err(|_| AocError::invalid(text, "Expected a number"))
+}
+
+/// Every integer in the text, in order, ignoring whatever is around them.
+/// A `-` directly before the digits counts as a sign, so `1-2` is `1` followed by `-2`.
+pub fn integers<T: FromStr>(text: &str) -> Result<Vec<T>> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = if i > 0 && bytes[i - 1] == b'-' {
+            i - 1
+        } else {
+            i
+        };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let number = &text[start..i];
+        let value = number
+            .parse()
+            .map_err(|_| AocError::invalid(text, &format!("{} is out of range", number)))?;
+        numbers.push(value);
+    }
+
+    Ok(numbers)
+}
+
+/// A line layout such as `#{id} @ {x},{y}`, where each `{name}` is a field and everything else
+/// has to appear exactly as written. A field runs up to the first place the text after it appears.
+#[derive(Debug)]
+pub struct Pattern<'p> {
+    pattern: &'p str,
+    literals: Vec<&'p str>,
+    names: Vec<&'p str>,
+}
+
+impl<'p> Pattern<'p> {
+    /// Panics if a brace is left open, or two fields have nothing between them, since then the
+    /// pattern itself is wrong rather than the input.
+    pub fn new(pattern: &'p str) -> Pattern<'p> {
+        let mut literals = Vec::new();
+        let mut names = Vec::new();
+        let mut rest = pattern;
+
+        while let Some(open) = rest.find('{') {
+            let close = rest[open..]
+                .find('}')
+                .unwrap_or_else(|| panic!("Unclosed field in pattern {:?}", pattern));
+            literals.push(&rest[..open]);
+            names.push(&rest[open + 1..open + close]);
+            rest = &rest[open + close + 1..];
+        }
+        literals.push(rest);
+
+        let between_fields = names.len().saturating_sub(1);
+        if literals
+            .iter()
+            .skip(1)
+            .take(between_fields)
+            .any(|l| l.is_empty())
+        {
+            panic!("Fields must be separated in pattern {:?}", pattern);
+        }
+
+        Pattern {
+            pattern,
+            literals,
+            names,
+        }
+    }
+
+    /// Splits a line into its fields, failing if the parts between them don't match.
+    pub fn parse<'t>(&self, line: &'t str) -> Result<Fields<'p, 't>> {
+        let mismatch =
+            || AocError::invalid(line, &format!("Expected a line like {}", self.pattern));
+
+        let mut rest = line.strip_prefix(self.literals[0]).ok_or_else(mismatch)?;
+        let mut values = Vec::new();
+        for literal in &self.literals[1..] {
+            let end = if literal.is_empty() {
+                rest.len()
+            } else {
+                rest.find(literal).ok_or_else(mismatch)?
+            };
+            values.push(&rest[..end]);
+            rest = &rest[end + literal.len()..];
+        }
+
+        if !rest.is_empty() {
+            return Err(mismatch());
+        }
+
+        Ok(Fields {
+            line,
+            names: self.names.clone(),
+            values,
+        })
+    }
+}
+
+/// The fields of a line that matched a `Pattern`.
+#[derive(Debug)]
+pub struct Fields<'p, 't> {
+    line: &'t str,
+    names: Vec<&'p str>,
+    values: Vec<&'t str>,
+}
+
+impl<'p, 't> Fields<'p, 't> {
+    /// The raw text of a field. Panics if the pattern has no field with that name.
+    pub fn get(&self, name: &str) -> &'t str {
+        match self.names.iter().position(|n| *n == name) {
+            Some(i) => self.values[i],
+            None => panic!("There is no field called {}", name),
+        }
+    }
+
+    /// Parses a field, ignoring whitespace around it. The error names the field that failed.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<T> {
+        let value = self.get(name);
+        value.trim().parse().map_err(|_| {
+            AocError::invalid(
+                self.line,
+                &format!("The {} field {:?} is not valid", name, value),
+            )
+        })
+    }
+}
+
+/// Splits lines into groups separated by one or more blank lines.
+pub fn blocks(lines: &[String]) -> Vec<&[String]> {
+    lines
+        .split(|l| l.trim().is_empty())
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+/// Loads lines of characters into a grid whose top-left character is at 0, 0.
+/// Every line has to be as long as the first.
+pub fn char_grid(lines: &[String]) -> Result<Grid<char>> {
+    let rows: Vec<Vec<char>> = lines.iter().map(|l| l.chars().collect()).collect();
+    let width = rows.first().map_or(0, |r| r.len());
+
+    if let Some(i) = rows.iter().position(|r| r.len() != width) {
+        return Err(AocError::invalid(
+            &lines[i],
+            &format!("Expected a row {} characters wide", width),
+        )
+        .at_line(i + 1));
+    }
+
+    Ok(Grid::from_fn(Point2::new(0, 0), width, rows.len(), |p| {
+        rows[p.y as usize][p.x as usize]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn should_parse_single_integer() {
+        assert_eq!(3, integer::<i32>("+3").unwrap());
+        assert_eq!(-12, integer::<i32>(" -12 ").unwrap());
+        assert!(integer::<i32>("+").is_err());
+        assert!(integer::<u8>("300").is_err());
+    }
+
+    #[test]
+    fn should_extract_integers() {
+        let claim: Vec<i32> = integers("#1 @ 1,3: 4x4").unwrap();
+        let position: Vec<i64> = integers("pos=<-2,0,10>, r=1-2").unwrap();
+
+        assert_eq!(vec![1, 1, 3, 4, 4], claim);
+        assert_eq!(vec![-2, 0, 10, 1, -2], position);
+        assert!(integers::<i32>("no numbers").unwrap().is_empty());
+        assert_eq!(
+            "-1 is out of range: \"a -1\"",
+            integers::<u32>("a -1").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn should_parse_fields_by_pattern() {
+        let pattern = Pattern::new("#{id} @ {x},{y}: {width}x{height}");
+        let fields = pattern.parse("#123 @ 3,2: 5x4").unwrap();
+
+        assert_eq!("123", fields.get("id"));
+        assert_eq!(2, fields.parse::<i32>("y").unwrap());
+        assert_eq!(4, fields.parse::<u32>("height").unwrap());
+
+        let fields = Pattern::new("[{time}] {message}")
+            .parse("[1518-11-01 00:00] falls asleep")
+            .unwrap();
+
+        assert_eq!("1518-11-01 00:00", fields.get("time"));
+        assert_eq!("falls asleep", fields.get("message"));
+    }
+
+    #[test]
+    fn should_name_failing_field() {
+        let pattern = Pattern::new("#{id} @ {x},{y}: {width}x{height}");
+        let fields = pattern.parse("#123 @ 3,2: 5x4x1").unwrap();
+
+        assert_eq!(
+            "The height field \"4x1\" is not valid: \"#123 @ 3,2: 5x4x1\"",
+            fields.parse::<u32>("height").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn should_reject_lines_not_matching_pattern() {
+        let pattern = Pattern::new("Guard #{id} begins shift");
+
+        assert!(pattern.parse("Guard #10 begins shift").is_ok());
+        assert!(pattern.parse("Guard #10 begins shift now").is_err());
+        assert!(pattern.parse("Guard 10 begins shift").is_err());
+        assert_eq!(
+            "Expected a line like Guard #{id} begins shift: \"falls asleep\"",
+            pattern.parse("falls asleep").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_refuse_adjacent_fields() {
+        Pattern::new("{x}{y}");
+    }
+
+    #[test]
+    fn should_split_blocks() {
+        let input = lines(&["", "a", "b", "", "", "c", " "]);
+
+        assert_eq!(vec![&input[1..3], &input[5..6]], blocks(&input));
+    }
+
+    #[test]
+    fn should_load_char_grid() {
+        let grid = char_grid(&lines(&["#.", ".#", "##"])).unwrap();
+
+        assert_eq!((2, 3), (grid.width(), grid.height()));
+        assert_eq!('#', grid[Point2::new(1, 1)]);
+        assert_eq!('.', grid[Point2::new(0, 1)]);
+        assert_eq!(
+            "2: Expected a row 2 characters wide: \"...\"",
+            char_grid(&lines(&["#.", "..."])).unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use super::{AocError, Result};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Defines a point type with the given fields. Points double as vectors, so one point can be added
+/// to another to move it. They are ordered by each field in turn, starting with `x`.
+macro_rules! point {
+    ($(#[$doc:meta])* $name:ident, $example:literal, $($field:ident),+) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+        pub struct $name {
+            $(pub $field: i32),+
+        }
+
+        impl $name {
+            pub const fn new($($field: i32),+) -> $name {
+                $name { $($field),+ }
+            }
+
+            /// The sum of the distances along each axis, as if moving around a city block.
+            pub fn manhattan(self, other: $name) -> i32 {
+                0 $(+ (self.$field - other.$field).abs())+
+            }
+
+            /// The largest distance along any one axis, as if moving like a king in chess.
+            pub fn chebyshev(self, other: $name) -> i32 {
+                0 $(.max((self.$field - other.$field).abs()))+
+            }
+
+            /// The straight-line distance.
+            pub fn euclidean(self, other: $name) -> f64 {
+                (0.0 $(+ f64::from(self.$field - other.$field).powi(2))+).sqrt()
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> $name {
+                $name { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i32> for $name {
+            type Output = $name;
+
+            fn mul(self, scale: i32) -> $name {
+                $name { $($field: self.$field * scale),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: $name) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: $name) {
+                *self = *self - other;
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let fields = [$(self.$field),+];
+                let text: Vec<String> = fields.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", text.join(", "))
+            }
+        }
+
+        /// Parses comma separated coordinates, with any amount of whitespace around each.
+        impl FromStr for $name {
+            type Err = AocError;
+
+            fn from_str(text: &str) -> Result<$name> {
+                let mut values = text.split(',').map(|v| {
+                    v.trim()
+                        .parse::<i32>()
+                        .map_err(|_| AocError::invalid(text, "Coordinate is not a number"))
+                });
+                let wrong_length = || {
+                    AocError::invalid(text, concat!("Expected a point like ", $example))
+                };
+
+                let point = $name {
+                    $($field: values.next().ok_or_else(wrong_length)??),+
+                };
+                if values.next().is_some() {
+                    return Err(wrong_length());
+                }
+
+                Ok(point)
+            }
+        }
+    };
+}
+
+point!(
+    /// A point on a plane, where y grows downwards.
+    Point2,
+    "1, 2",
+    x,
+    y
+);
+point!(
+    /// A point in space.
+    Point3,
+    "1, 2, 3",
+    x,
+    y,
+    z
+);
+point!(
+    /// A point in four dimensions.
+    Point4,
+    "1, 2, 3, 4",
+    x,
+    y,
+    z,
+    w
+);
+
+impl From<(i32, i32)> for Point2 {
+    fn from((x, y): (i32, i32)) -> Point2 {
+        Point2 { x, y }
+    }
+}
+
+impl From<(i32, i32, i32)> for Point3 {
+    fn from((x, y, z): (i32, i32, i32)) -> Point3 {
+        Point3 { x, y, z }
+    }
+}
+
+impl From<(i32, i32, i32, i32)> for Point4 {
+    fn from((x, y, z, w): (i32, i32, i32, i32)) -> Point4 {
+        Point4 { x, y, z, w }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_measure_distances() {
+        let a = Point2::new(1, 1);
+        let b = Point2::new(5, 0);
+
+        assert_eq!(5, a.manhattan(b));
+        assert_eq!(5, Point2::new(5, 5).manhattan(b));
+        assert_eq!(4, a.chebyshev(b));
+        assert_eq!(17f64.sqrt(), a.euclidean(b));
+        assert_eq!(5.0, Point3::new(0, 0, 0).euclidean(Point3::new(0, 3, 4)));
+        assert_eq!(
+            10,
+            Point4::new(1, -1, 2, 0).manhattan(Point4::new(-2, 1, 0, 3))
+        );
+    }
+
+    #[test]
+    fn should_do_arithmetic() {
+        let mut point = Point3::new(1, 2, 3);
+        point += Point3::new(1, 1, 1);
+
+        assert_eq!(Point3::new(2, 3, 4), point);
+        assert_eq!(Point3::new(0, 0, 0), point - point);
+        assert_eq!(Point3::new(-4, -6, -8), -point * 2);
+    }
+
+    #[test]
+    fn should_order_by_x_first() {
+        let mut points = vec![Point2::new(1, 0), Point2::new(0, 5), Point2::new(0, 1)];
+        points.sort();
+
+        assert_eq!(
+            vec![Point2::new(0, 1), Point2::new(0, 5), Point2::new(1, 0)],
+            points
+        );
+    }
+
+    #[test]
+    fn should_parse_points() {
+        assert_eq!(Point2::new(46, 246), "46, 246".parse().unwrap());
+        assert_eq!(Point4::new(-1, 2, 0, -3), " -1,2, 0 ,-3".parse().unwrap());
+        assert_eq!("46, 246", Point2::new(46, 246).to_string());
+    }
+
+    #[test]
+    fn should_reject_invalid_points() {
+        let error = "46".parse::<Point2>().unwrap_err();
+
+        assert_eq!("Expected a point like 1, 2: \"46\"", error.to_string());
+        assert!("46, 246, 1".parse::<Point2>().is_err());
+        assert!("46, x".parse::<Point2>().is_err());
+        assert!("1, 2".parse::<Point3>().is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use super::frequency::Frequency;
+use super::{parse_frequency_change, split_changes};
+use crate::common::{AocError, Result};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+/// Follows the frequency as changes arrive one at a time, so the changes don't all need to be
+/// known up front and can come from a log that is still being written.
+#[derive(Debug)]
+pub struct FrequencyDevice<T: Frequency = i64> {
+    frequency: T,
+    visits: HashMap<T, u32>,
+    first_repeat: Option<T>,
+    changes: usize,
+}
+
+impl<T: Frequency> FrequencyDevice<T> {
+    pub fn new(start: T) -> FrequencyDevice<T> {
+        let mut visits = HashMap::new();
+        visits.insert(start.clone(), 1);
+
+        FrequencyDevice {
+            frequency: start,
+            visits,
+            first_repeat: None,
+            changes: 0,
+        }
+    }
+
+    pub fn frequency(&self) -> T {
+        self.frequency.clone()
+    }
+
+    /// How many times each frequency has been reached, counting the start.
+    pub fn visits(&self) -> &HashMap<T, u32> {
+        &self.visits
+    }
+
+    pub fn visits_to(&self, frequency: &T) -> u32 {
+        self.visits.get(frequency).copied().unwrap_or(0)
+    }
+
+    /// The first frequency that was reached a second time, if any has been yet.
+    pub fn first_repeat(&self) -> Option<T> {
+        self.first_repeat.clone()
+    }
+
+    /// How many changes have been applied.
+    pub fn changes(&self) -> usize {
+        self.changes
+    }
+
+    /// Applies a change such as `+3`. Returns the frequency if this is the change that first
+    /// repeats one, so callers can react the moment it happens.
+    pub fn apply(&mut self, change: &str) -> Result<Option<T>> {
+        self.frequency = parse_frequency_change(change, self.frequency.clone())?;
+        self.changes += 1;
+
+        let visits = self.visits.entry(self.frequency.clone()).or_insert(0);
+        *visits += 1;
+        if *visits == 2 && self.first_repeat.is_none() {
+            self.first_repeat = Some(self.frequency.clone());
+            return Ok(self.first_repeat.clone());
+        }
+
+        Ok(None)
+    }
+
+    /// Applies the changes on one line, in any of the formats `split_changes` accepts. Returns the
+    /// first repeat if it happens on this line, stopping right after it if `stop_at_repeat`.
+    fn apply_line(&mut self, line: &str, stop_at_repeat: bool) -> Result<Option<T>> {
+        let mut repeat = None;
+        for (column, change) in split_changes(line) {
+            if let Some(frequency) = self.apply(change).map_err(|e| e.at_column(column))? {
+                repeat = Some(frequency);
+                if stop_at_repeat {
+                    break;
+                }
+            }
+        }
+
+        Ok(repeat)
+    }
+
+    /// Applies every line of changes in turn. Errors give the (1-indexed) position of the bad
+    /// line among those passed in.
+    pub fn feed<I, S>(&mut self, lines: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for (i, line) in lines.into_iter().enumerate() {
+            self.apply_line(line.as_ref(), false)
+                .map_err(|e| e.at_line(i + 1))?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies changes until a frequency repeats, and returns it. Fed an endless iterator such as
+    /// `cycle()`, this only returns once a repeat is found.
+    pub fn feed_until_repeat<I, S>(&mut self, lines: I) -> Result<Option<T>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if self.first_repeat.is_some() {
+            return Ok(self.first_repeat.clone());
+        }
+
+        for (i, line) in lines.into_iter().enumerate() {
+            let repeat = self
+                .apply_line(line.as_ref(), true)
+                .map_err(|e| e.at_line(i + 1))?;
+            if repeat.is_some() {
+                return Ok(repeat);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Applies each line of a reader, such as stdin, as it arrives. `source` names the reader in
+    /// errors.
+    pub fn read_from<R: BufRead>(&mut self, source: &str, reader: R) -> Result<()> {
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| AocError::Io {
+                file: source.to_string(),
+                source: e,
+            })?;
+            self.apply_line(&line, false)
+                .map_err(|e| e.at_line(i + 1).in_file(source))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn should_follow_frequency() {
+        let mut device = FrequencyDevice::new(0);
+        device.feed(["+1", "-2", "+3"]).unwrap();
+
+        assert_eq!(2, device.frequency());
+        assert_eq!(3, device.changes());
+        assert_eq!(None, device.first_repeat());
+    }
+
+    #[test]
+    fn should_count_visits() {
+        let mut device = FrequencyDevice::new(0);
+        device.feed(["+1", "-1", "+1", "-1"]).unwrap();
+
+        assert_eq!(3, device.visits_to(&0));
+        assert_eq!(2, device.visits_to(&1));
+        assert_eq!(0, device.visits_to(&5));
+        assert_eq!(2, device.visits().len());
+    }
+
+    #[test]
+    fn should_report_first_repeat_as_it_happens() {
+        let mut device = FrequencyDevice::new(0);
+
+        assert_eq!(None, device.apply("+3").unwrap());
+        assert_eq!(None, device.apply("-1").unwrap());
+        assert_eq!(Some(3), device.apply("+1").unwrap());
+        assert_eq!(None, device.apply("-3").unwrap());
+        assert_eq!(Some(3), device.first_repeat());
+    }
+
+    #[test]
+    fn should_stop_at_first_repeat() {
+        let changes = ["+3", "+3", "+4", "-2", "-4"];
+        let mut device = FrequencyDevice::new(0);
+
+        assert_eq!(
+            Some(10),
+            device.feed_until_repeat(changes.iter().cycle()).unwrap()
+        );
+        assert_eq!(7, device.changes());
+    }
+
+    #[test]
+    fn should_read_lines_from_reader() {
+        let mut device = FrequencyDevice::new(0);
+        device
+            .read_from("<stdin>", Cursor::new("+7\n+7\n-2\n"))
+            .unwrap();
+
+        assert_eq!(12, device.frequency());
+
+        let error = device
+            .read_from("<stdin>", Cursor::new("+1\nx\n"))
+            .unwrap_err();
+
+        assert_eq!(
+            "<stdin>:2:1: Expected a + or - modifier: \"x\"",
+            error.to_string()
+        );
+        assert_eq!(13, device.frequency());
+    }
+}
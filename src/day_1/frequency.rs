@@ -0,0 +1,103 @@
+use crate::common::BigInt;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Which `Frequency` to solve with, for inputs that go past the range of the default `i64`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FrequencyType {
+    I32,
+    #[default]
+    I64,
+    I128,
+    Big,
+}
+
+impl FromStr for FrequencyType {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<FrequencyType, String> {
+        match name {
+            "i32" => Ok(FrequencyType::I32),
+            "i64" => Ok(FrequencyType::I64),
+            "i128" => Ok(FrequencyType::I128),
+            "big" => Ok(FrequencyType::Big),
+            other => Err(format!("Unknown frequency type: {}", other)),
+        }
+    }
+}
+
+/// A number type frequencies can be tracked in. The fixed size integers report overflow by
+/// returning `None`, where `BigInt` simply grows.
+pub trait Frequency: Clone + Eq + Ord + Hash + Debug + Display + FromStr {
+    /// The type's name, for error messages.
+    const NAME: &'static str;
+
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    /// The quotient and the never negative remainder. `None` when dividing by zero, or when the
+    /// quotient doesn't fit.
+    fn checked_div_rem_euclid(&self, other: &Self) -> Option<(Self, Self)>;
+}
+
+macro_rules! primitive_frequency {
+    ($($type:ident),+) => {
+        $(
+            impl Frequency for $type {
+                const NAME: &'static str = stringify!($type);
+
+                fn zero() -> $type {
+                    0
+                }
+
+                fn one() -> $type {
+                    1
+                }
+
+                fn checked_add(&self, other: &$type) -> Option<$type> {
+                    $type::checked_add(*self, *other)
+                }
+
+                fn checked_sub(&self, other: &$type) -> Option<$type> {
+                    $type::checked_sub(*self, *other)
+                }
+
+                fn checked_div_rem_euclid(&self, other: &$type) -> Option<($type, $type)> {
+                    Some((self.checked_div_euclid(*other)?, self.checked_rem_euclid(*other)?))
+                }
+            }
+        )+
+    };
+}
+
+primitive_frequency!(i32, i64, i128);
+
+impl Frequency for BigInt {
+    const NAME: &'static str = "BigInt";
+
+    fn zero() -> BigInt {
+        BigInt::zero()
+    }
+
+    fn one() -> BigInt {
+        BigInt::from(1)
+    }
+
+    fn checked_add(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &BigInt) -> Option<BigInt> {
+        Some(self - other)
+    }
+
+    fn checked_div_rem_euclid(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        self.div_rem_euclid(other)
+    }
+}
@@ -1,75 +1,248 @@
-use crate::common;
-use std::collections::HashMap;
+use crate::common::{self, AocError, BigInt, Result};
+use crate::solution::{Answer, Day, Solution};
+use device::FrequencyDevice;
+use frequency::{Frequency, FrequencyType};
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+pub mod device;
+pub mod frequency;
+pub mod report;
 
-fn parse_frequency_change(input: &str, value: i32) -> i32 {
-    let modifier = &input[..1];
-    let change: i32 = input[1..].parse().unwrap();
+/// Applies a single change, such as `+3`, `-2` or just `3`, to the current frequency.
+pub fn parse_frequency_change<T: Frequency>(input: &str, value: T) -> Result<T> {
+    let change: T = match input.chars().next() {
+        Some('+') | Some('-') | Some('0'..='9') => common::integer(input).map_err(|_| {
+            let digits = input.strip_prefix(&['+', '-'][..]).unwrap_or(input);
+            if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                let reason = format!("Frequency change does not fit in {}", T::NAME);
+                AocError::invalid(input, &reason)
+            } else {
+                AocError::invalid(input, "Frequency change is not a number")
+            }
+        })?,
+        Some(_) => return Err(AocError::invalid(input, "Expected a + or - modifier")),
+        None => {
+            return Err(AocError::invalid(
+                input,
+                "Expected a signed frequency change",
+            ))
+        }
+    };
 
-    if modifier == "+" {
-        return value + change;
-    } else if modifier == "-" {
-        return value - change;
-    }
-    0
+    value.checked_add(&change).ok_or_else(|| {
+        let reason = format!("Frequency goes past the range of {}", T::NAME);
+        AocError::invalid(input, &reason)
+    })
 }
 
-fn calculate_total(start: i32, instructions: Vec<String>) -> i32 {
-    let mut result = start;
+/// The changes on one line, each with the (1-indexed) column it starts at. Changes can be
+/// separated by commas or whitespace, as in `+1, -2, +3`, and anything after a `#` is a comment.
+pub fn split_changes(line: &str) -> Vec<(usize, &str)> {
+    let line = line.split('#').next().unwrap_or("");
+    let mut changes = Vec::new();
+    let mut start = None;
 
-    for s in instructions.iter() {
-        result = parse_frequency_change(s, result);
+    for (column, (i, c)) in line.char_indices().enumerate() {
+        if c == ',' || c.is_whitespace() {
+            if let Some((column, from)) = start.take() {
+                changes.push((column + 1, &line[from..i]));
+            }
+        } else if start.is_none() {
+            start = Some((column, i));
+        }
+    }
+    if let Some((column, from)) = start {
+        changes.push((column + 1, &line[from..]));
     }
 
-    result
+    changes
+}
+
+/// Every change in a list, each with the (1-indexed) line and column it starts at. Blank lines
+/// and comments have none.
+pub fn list_changes<S: AsRef<str>>(lines: &[S]) -> Vec<(usize, usize, &str)> {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            split_changes(line.as_ref())
+                .into_iter()
+                .map(move |(column, change)| (i + 1, column, change))
+        })
+        .collect()
+}
+
+/// The frequency after applying every change once.
+pub fn calculate_total<T: Frequency>(start: T, instructions: Vec<String>) -> Result<T> {
+    let mut device = FrequencyDevice::new(start);
+    device.feed(&instructions)?;
+    Ok(device.frequency())
 }
 
-fn calculate_first_repetition(start: i32, instructions: Vec<String>) -> i32 {
-    let mut map: HashMap<i32, u32> = HashMap::new();
-    let mut result = start;
+/// Where the frequency first repeats: the frequency itself, the pass through the changes it
+/// happens on and the index of the change about to be applied, both counting from 0.
+#[derive(PartialEq, Debug)]
+pub struct Repetition<T> {
+    pub frequency: T,
+    pub pass: T,
+    pub index: usize,
+}
+
+/// Works out where the frequency first repeats without cycling through the changes, so it stays
+/// fast however many passes that takes. `None` if no frequency is ever reached twice.
+pub fn find_first_repetition<T: Frequency>(
+    start: T,
+    instructions: &[String],
+) -> Result<Option<Repetition<T>>> {
+    // The frequency before each change on the first pass.
+    let changes = list_changes(instructions);
+    let mut frequencies = Vec::with_capacity(changes.len());
+    let mut current = start.clone();
+    for (line, column, change) in changes {
+        frequencies.push(current.clone());
+        current = parse_frequency_change(change, current)
+            .map_err(|e| e.at_line(line).at_column(column))?;
+    }
+
+    let mut seen = HashSet::new();
+    for (index, frequency) in frequencies.iter().enumerate() {
+        if !seen.insert(frequency) {
+            return Ok(Some(Repetition {
+                frequency: frequency.clone(),
+                pass: T::zero(),
+                index,
+            }));
+        }
+    }
+
+    let too_far = || {
+        let reason = format!(
+            "The frequencies are too far apart to compare in {}",
+            T::NAME
+        );
+        AocError::no_solution(&reason)
+    };
+    let drift = current.checked_sub(&start).ok_or_else(too_far)?;
+    if frequencies.is_empty() {
+        return Ok(None);
+    } else if drift == T::zero() {
+        return Ok(Some(Repetition {
+            frequency: start,
+            pass: T::one(),
+            index: 0,
+        }));
+    }
+
+    // On pass k the frequency before change j is frequencies[j] + k * drift, so it can only ever
+    // reach a frequency that leaves the same remainder when divided by the drift. Of those, the
+    // nearest one in the direction of the drift is reached first.
+    let mut groups: HashMap<T, Vec<(&T, usize)>> = HashMap::new();
+    for (index, frequency) in frequencies.iter().enumerate() {
+        let (_, remainder) = frequency
+            .checked_div_rem_euclid(&drift)
+            .ok_or_else(too_far)?;
+        groups
+            .entry(remainder)
+            .or_default()
+            .push((frequency, index));
+    }
 
-    loop {
-        for s in instructions.iter() {
-            match map.get(&result) {
-                Some(_) => return result,
-                None => map.insert(result, 0),
-            };
+    let mut first: Option<Repetition<T>> = None;
+    for group in groups.values_mut() {
+        group.sort_unstable();
+        if drift < T::zero() {
+            group.reverse();
+        }
 
-            result = parse_frequency_change(s, result);
+        for pair in group.windows(2) {
+            let ((from, index), (to, _)) = (pair[0], pair[1]);
+            let (pass, _) = to
+                .checked_sub(from)
+                .and_then(|distance| distance.checked_div_rem_euclid(&drift))
+                .ok_or_else(too_far)?;
+            if first
+                .as_ref()
+                .is_none_or(|f| (&pass, index) < (&f.pass, f.index))
+            {
+                first = Some(Repetition {
+                    frequency: to.clone(),
+                    pass,
+                    index,
+                });
+            }
         }
     }
+
+    Ok(first)
 }
 
-pub fn part_1() {
-    let filename = "./inputs/day_1/input.txt";
+/// The first frequency reached twice, cycling through the changes as many times as needed.
+pub fn calculate_first_repetition<T: Frequency>(start: T, instructions: Vec<String>) -> Result<T> {
+    if list_changes(&instructions).is_empty() {
+        return Err(AocError::no_solution("There are no frequency changes"));
+    }
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let instructions = lines.map(|l| l.expect("Could not parse line")).collect();
-        println!("Day 1 - Part 1: {}", calculate_total(0, instructions));
+    find_first_repetition(start, &instructions)?
+        .map(|repetition| repetition.frequency)
+        .ok_or_else(|| AocError::no_solution("No frequency is ever reached twice"))
+}
+
+/// Day 1, with frequencies tracked in `T`.
+pub struct Calibration<T>(PhantomData<T>);
+
+/// Day 1 as registered, with frequencies tracked in `i64`.
+pub type ChronalCalibration = Calibration<i64>;
+
+/// Day 1 with frequencies tracked in the given type, to stand in for the registered day.
+pub fn day(frequency: FrequencyType) -> Day {
+    let title = "Chronal Calibration";
+    match frequency {
+        FrequencyType::I32 => {
+            Day::new::<Calibration<i32>>(1, title).with_solve_args(&["--frequency", "i32"])
+        }
+        FrequencyType::I64 => {
+            Day::new::<Calibration<i64>>(1, title).with_solve_args(&["--frequency", "i64"])
+        }
+        FrequencyType::I128 => {
+            Day::new::<Calibration<i128>>(1, title).with_solve_args(&["--frequency", "i128"])
+        }
+        FrequencyType::Big => {
+            Day::new::<Calibration<BigInt>>(1, title).with_solve_args(&["--frequency", "big"])
+        }
     }
 }
 
-pub fn part_2() {
-    let filename = "./inputs/day_1/input.txt";
+impl<T: Frequency + Into<Answer>> Solution for Calibration<T> {
+    type Input = Vec<String>;
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let instructions = lines.map(|l| l.expect("Could not parse line")).collect();
-        println!(
-            "Day 1 - Part 2: {}",
-            calculate_first_repetition(0, instructions)
-        );
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        for (line, column, change) in list_changes(&lines) {
+            parse_frequency_change(change, T::zero())
+                .map_err(|e| e.at_line(line).at_column(column))?;
+        }
+        Ok(lines)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(calculate_total(T::zero(), input.clone())?.into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(calculate_first_repetition(T::zero(), input.clone())?.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::BigInt;
 
     #[test]
     fn should_increment_value() {
         let input = "+1";
         let value = 0;
 
-        assert_eq!(1, parse_frequency_change(&input, value));
+        assert_eq!(1, parse_frequency_change(input, value).unwrap());
     }
 
     #[test]
@@ -77,7 +250,165 @@ mod tests {
         let input = "-4";
         let value = 0;
 
-        assert_eq!(-4, parse_frequency_change(&input, value));
+        assert_eq!(-4, parse_frequency_change(input, value).unwrap());
+    }
+
+    #[test]
+    fn should_reject_invalid_changes() {
+        assert!(parse_frequency_change("", 0).is_err());
+        assert!(parse_frequency_change("+", 0).is_err());
+        assert!(parse_frequency_change("+x", 0).is_err());
+        assert!(parse_frequency_change("*3", 0).is_err());
+    }
+
+    #[test]
+    fn should_accept_unsigned_positive_changes() {
+        assert_eq!(4, parse_frequency_change("3", 1).unwrap());
+        assert!(parse_frequency_change("3x", 1).is_err());
+    }
+
+    #[test]
+    fn should_split_lists_of_changes() {
+        assert_eq!(
+            vec![(1, "+1"), (5, "-2"), (9, "+3"), (13, "+1")],
+            split_changes("+1, -2, +3, +1")
+        );
+        assert_eq!(vec![(2, "+1"), (6, "2")], split_changes(" +1\t 2 # +5, -5"));
+        assert!(split_changes("# just a comment").is_empty());
+        assert!(split_changes("").is_empty());
+    }
+
+    #[test]
+    fn should_read_any_list_format() {
+        let lines = vec![
+            "# The example from the puzzle".to_string(),
+            "+1, -2, +3, +1".to_string(),
+            "".to_string(),
+            "-1 2".to_string(),
+        ];
+
+        assert_eq!(4, calculate_total(0, lines.clone()).unwrap());
+        assert_eq!(2, calculate_first_repetition(0, lines).unwrap());
+    }
+
+    #[test]
+    fn should_report_line_and_column_of_invalid_change() {
+        let lines = vec![
+            "+1, -2".to_string(),
+            "# comment".to_string(),
+            "+3, x4".to_string(),
+        ];
+        let error = ChronalCalibration::parse(lines).unwrap_err();
+
+        assert_eq!("3:5: Expected a + or - modifier: \"x4\"", error.to_string());
+    }
+
+    #[test]
+    fn should_report_overflow() {
+        let error = parse_frequency_change("+1", i32::MAX).unwrap_err();
+        assert_eq!(
+            "Frequency goes past the range of i32: \"+1\"",
+            error.to_string()
+        );
+
+        let error = parse_frequency_change("+3000000000", 0i32).unwrap_err();
+        assert_eq!(
+            "Frequency change does not fit in i32: \"+3000000000\"",
+            error.to_string()
+        );
+
+        assert_eq!(
+            3_000_000_000i64,
+            parse_frequency_change("+3000000000", 0i64).unwrap()
+        );
+        assert!(parse_frequency_change("-1", i64::MIN).is_err());
+    }
+
+    #[test]
+    fn should_track_frequencies_in_any_integer_type() {
+        let changes = changes(&[i32::MAX, i32::MAX, i32::MAX]);
+
+        assert!(calculate_total(0i32, changes.clone()).is_err());
+        assert_eq!(
+            3 * i64::from(i32::MAX),
+            calculate_total(0i64, changes.clone()).unwrap()
+        );
+        assert_eq!(
+            3 * i128::from(i32::MAX),
+            calculate_total(0i128, changes.clone()).unwrap()
+        );
+        assert_eq!(
+            "6442450941",
+            calculate_total(BigInt::zero(), changes)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn should_go_past_i128_with_big_integers() {
+        let huge = format!("+{}", i128::MAX);
+        let changes = vec![huge.clone(), huge, "-1".to_string()];
+        let total = calculate_total(BigInt::zero(), changes.clone()).unwrap();
+
+        assert_eq!("340282366920938463463374607431768211453", total.to_string());
+        assert!(calculate_total(0i128, changes).is_err());
+    }
+
+    #[test]
+    fn should_solve_past_i64_in_wider_frequency_types() {
+        let up = format!("+{}", i64::MAX);
+        let down = format!("-{}", i64::MAX);
+        let solve = |frequency, part, changes: &[&String]| {
+            let day = day(frequency);
+            let lines = changes.iter().map(|c| c.to_string()).collect();
+            day.parse(lines)
+                .and_then(|input| day.solve(part, &input))
+                .map(|result| result.answer)
+        };
+
+        assert!(solve(FrequencyType::I32, 1, &[&up]).is_err());
+        let error = solve(FrequencyType::I64, 1, &[&up, &up]).unwrap_err();
+        assert_eq!(
+            "2:1: Frequency goes past the range of i64: \"+9223372036854775807\"",
+            error.to_string()
+        );
+        for frequency in [FrequencyType::I128, FrequencyType::Big] {
+            assert_eq!(
+                Answer::Text("18446744073709551614".to_string()),
+                solve(frequency, 1, &[&up, &up]).unwrap()
+            );
+            assert_eq!(
+                Answer::Integer(i64::MAX),
+                solve(frequency, 2, &[&up, &up, &down]).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn should_find_repetition_with_big_integers() {
+        let changes: Vec<String> = ["+3", "+3", "+4", "-2", "-4"]
+            .iter()
+            .map(|c| format!("{}000000000000000000000000", c))
+            .collect();
+        let result = find_first_repetition(BigInt::zero(), &changes)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!("10000000000000000000000000", result.frequency.to_string());
+        assert_eq!(BigInt::from(1), result.pass);
+        assert_eq!(2, result.index);
+    }
+
+    #[test]
+    fn should_report_line_of_invalid_change() {
+        let list = vec![String::from("+1"), String::from("+x")];
+        let error = calculate_total(0, list).unwrap_err();
+
+        assert_eq!(
+            "2:1: Frequency change is not a number: \"+x\"",
+            error.to_string()
+        );
     }
 
     #[test]
@@ -89,26 +420,26 @@ mod tests {
             String::from("+1"),
         ];
         let answer1 = 3;
-        assert_eq!(answer1, calculate_total(0, list1));
+        assert_eq!(answer1, calculate_total(0, list1).unwrap());
 
         let list2 = vec![String::from("+1"), String::from("+1"), String::from("+1")];
         let answer2 = 3;
-        assert_eq!(answer2, calculate_total(0, list2));
+        assert_eq!(answer2, calculate_total(0, list2).unwrap());
 
         let list3 = vec![String::from("+1"), String::from("+1"), String::from("-2")];
         let answer3 = 0;
-        assert_eq!(answer3, calculate_total(0, list3));
+        assert_eq!(answer3, calculate_total(0, list3).unwrap());
 
         let list4 = vec![String::from("-1"), String::from("-2"), String::from("-3")];
         let answer4 = -6;
-        assert_eq!(answer4, calculate_total(0, list4));
+        assert_eq!(answer4, calculate_total(0, list4).unwrap());
     }
 
     #[test]
     fn should_find_same_value_twice() {
         let list1 = vec![String::from("+1"), String::from("-1")];
         let answer1 = 0;
-        assert_eq!(answer1, calculate_first_repetition(0, list1));
+        assert_eq!(answer1, calculate_first_repetition(0, list1).unwrap());
 
         let list2 = vec![
             String::from("+3"),
@@ -118,7 +449,7 @@ mod tests {
             String::from("-4"),
         ];
         let answer2 = 10;
-        assert_eq!(answer2, calculate_first_repetition(0, list2));
+        assert_eq!(answer2, calculate_first_repetition(0, list2).unwrap());
 
         let list3 = vec![
             String::from("-6"),
@@ -128,7 +459,7 @@ mod tests {
             String::from("-6"),
         ];
         let answer3 = 5;
-        assert_eq!(answer3, calculate_first_repetition(0, list3));
+        assert_eq!(answer3, calculate_first_repetition(0, list3).unwrap());
 
         let list4 = vec![
             String::from("+7"),
@@ -138,6 +469,108 @@ mod tests {
             String::from("-4"),
         ];
         let answer4 = 14;
-        assert_eq!(answer4, calculate_first_repetition(0, list4));
+        assert_eq!(answer4, calculate_first_repetition(0, list4).unwrap());
+    }
+
+    fn changes(values: &[i32]) -> Vec<String> {
+        values.iter().map(|v| format!("{:+}", v)).collect()
+    }
+
+    /// Cycles through the changes the slow way, giving up after a number of passes.
+    fn simulate(start: i32, values: &[i32], passes: i32) -> Option<Repetition<i32>> {
+        let mut seen = HashSet::new();
+        let mut frequency = start;
+
+        for pass in 0..passes {
+            for (index, change) in values.iter().enumerate() {
+                if !seen.insert(frequency) {
+                    return Some(Repetition {
+                        frequency,
+                        pass,
+                        index,
+                    });
+                }
+                frequency += change;
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn should_locate_first_repetition() {
+        assert_eq!(
+            Some(Repetition {
+                frequency: 0,
+                pass: 1,
+                index: 0
+            }),
+            find_first_repetition(0, &changes(&[1, -1])).unwrap()
+        );
+        assert_eq!(
+            Some(Repetition {
+                frequency: 10,
+                pass: 1,
+                index: 2
+            }),
+            find_first_repetition(0, &changes(&[3, 3, 4, -2, -4])).unwrap()
+        );
+        assert_eq!(
+            Some(Repetition {
+                frequency: 6,
+                pass: 0,
+                index: 4
+            }),
+            find_first_repetition(5, &changes(&[1, 2, -1, -1, 5])).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_predict_repetition_after_many_passes() {
+        let result = find_first_repetition(0, &changes(&[10_000_000, -9_999_999])).unwrap();
+
+        assert_eq!(
+            Some(Repetition {
+                frequency: 10_000_000,
+                pass: 10_000_000,
+                index: 0
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn should_find_no_repetition() {
+        assert_eq!(None, find_first_repetition(0, &changes(&[1])).unwrap());
+        assert_eq!(
+            None,
+            find_first_repetition(0, &changes(&[2, 2, -1])).unwrap()
+        );
+        assert_eq!(None, find_first_repetition(0, &[]).unwrap());
+
+        let error = calculate_first_repetition(0, changes(&[1, 2])).unwrap_err();
+        assert_eq!(
+            "No solution: No frequency is ever reached twice",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn should_match_simulation() {
+        let values = [-3, -2, -1, 1, 2, 3];
+        for a in values.iter() {
+            for b in values.iter() {
+                for c in values.iter() {
+                    let input = [*a, *b, *c, -4];
+                    let expected = simulate(0, &input, 20);
+                    let result = find_first_repetition(0, &changes(&input)).unwrap();
+
+                    match (expected, result) {
+                        (None, Some(r)) => assert!(r.pass >= 20, "{:?}: {:?}", input, r),
+                        (expected, result) => assert_eq!(expected, result, "{:?}", input),
+                    }
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,267 @@
+use super::device::FrequencyDevice;
+use super::frequency::Frequency;
+use super::{calculate_total, find_first_repetition, list_changes, Repetition};
+use crate::common::{AocError, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+
+/// One change that was followed, and the frequency it led to.
+#[derive(PartialEq, Debug)]
+pub struct Step<T> {
+    /// Counted in `T`, like the pass of a `Repetition`.
+    pub pass: T,
+    pub index: usize,
+    pub change: String,
+    pub frequency: T,
+}
+
+/// How the device behaves on a list of changes. The range and visits come from following the
+/// changes until the first repeat, or until `limit` changes if that comes sooner, while the drift
+/// and the first repeat are worked out directly, however far away it is.
+#[derive(Debug)]
+pub struct Report<T> {
+    /// The number of changes in one pass through the list.
+    pub changes: usize,
+    pub drift: T,
+    /// How many changes were followed.
+    pub followed: usize,
+    /// Whether following stopped at the limit before reaching the first repeat.
+    pub stopped: bool,
+    pub min: T,
+    pub max: T,
+    /// How many frequencies were reached once, twice, and so on.
+    pub histogram: BTreeMap<u32, usize>,
+    pub first_repeat: Option<Repetition<T>>,
+    /// Every change followed, if the trace was asked for.
+    pub trace: Vec<Step<T>>,
+}
+
+impl<T: Frequency> Report<T> {
+    pub fn new(start: T, lines: &[String], limit: usize, trace: bool) -> Result<Report<T>> {
+        let drift = calculate_total(start.clone(), lines.to_vec())?
+            .checked_sub(&start)
+            .ok_or_else(|| {
+                let reason = format!("The drift per pass does not fit in {}", T::NAME);
+                AocError::no_solution(&reason)
+            })?;
+        let first_repeat = find_first_repetition(start.clone(), lines)?;
+
+        let changes = list_changes(lines);
+        let mut device = FrequencyDevice::new(start.clone());
+        let (mut min, mut max) = (start.clone(), start);
+        let mut steps = Vec::new();
+        let mut pass = T::zero();
+
+        'passes: loop {
+            for (index, (_, _, change)) in changes.iter().enumerate() {
+                if device.changes() == limit || device.first_repeat().is_some() {
+                    break 'passes;
+                }
+
+                // Every change was already checked by working out the drift.
+                device.apply(change)?;
+                let frequency = device.frequency();
+                if frequency < min {
+                    min = frequency.clone();
+                } else if frequency > max {
+                    max = frequency.clone();
+                }
+
+                if trace {
+                    steps.push(Step {
+                        pass: pass.clone(),
+                        index,
+                        change: change.to_string(),
+                        frequency,
+                    });
+                }
+            }
+
+            if changes.is_empty() {
+                break;
+            }
+            pass = pass.checked_add(&T::one()).ok_or_else(|| {
+                let reason = format!("The number of passes does not fit in {}", T::NAME);
+                AocError::no_solution(&reason)
+            })?;
+        }
+
+        let mut histogram = BTreeMap::new();
+        for visits in device.visits().values() {
+            *histogram.entry(*visits).or_insert(0) += 1;
+        }
+
+        Ok(Report {
+            changes: changes.len(),
+            drift,
+            followed: device.changes(),
+            stopped: device.first_repeat().is_none() && !changes.is_empty(),
+            min,
+            max,
+            histogram,
+            first_repeat,
+            trace: steps,
+        })
+    }
+
+    /// Writes the trace as CSV, with a header row.
+    pub fn write_trace<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "step,pass,index,change,frequency")?;
+        for (i, step) in self.trace.iter().enumerate() {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                i + 1,
+                step.pass,
+                step.index,
+                step.change,
+                step.frequency
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Frequency> fmt::Display for Report<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let count = |count: usize, one: &str, many: &str| {
+            format!("{} {}", count, if count == 1 { one } else { many })
+        };
+
+        writeln!(f, "Changes per pass: {}", self.changes)?;
+        writeln!(f, "Drift per pass: {}", self.drift)?;
+        write!(
+            f,
+            "Range: {} to {} over {}",
+            self.min,
+            self.max,
+            count(self.followed, "change", "changes")
+        )?;
+        if self.stopped {
+            write!(f, ", where following stopped")?;
+        }
+        writeln!(f)?;
+
+        match &self.first_repeat {
+            Some(repeat) => writeln!(
+                f,
+                "First repeat: {}, before change {} of pass {} (both counting from 0)",
+                repeat.frequency, repeat.index, repeat.pass
+            )?,
+            None => writeln!(f, "First repeat: never")?,
+        }
+
+        writeln!(f, "Visits:")?;
+        for (visits, frequencies) in self.histogram.iter() {
+            writeln!(
+                f,
+                "  {}: {}",
+                count(*visits as usize, "time", "times"),
+                count(*frequencies, "frequency", "frequencies")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::BigInt;
+
+    fn lines(changes: &str) -> Vec<String> {
+        vec![changes.to_string()]
+    }
+
+    #[test]
+    fn should_follow_changes_until_first_repeat() {
+        let report = Report::new(0, &lines("+3, +3, +4, -2, -4"), 1000, true).unwrap();
+
+        assert_eq!(5, report.changes);
+        assert_eq!(4, report.drift);
+        assert_eq!(7, report.followed);
+        assert_eq!((0, 10), (report.min, report.max));
+        assert_eq!(
+            Some(Repetition {
+                frequency: 10,
+                pass: 1,
+                index: 2
+            }),
+            report.first_repeat
+        );
+        assert_eq!(
+            vec![(1, 6), (2, 1)],
+            report.histogram.into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Step {
+                pass: 1,
+                index: 1,
+                change: "+3".to_string(),
+                frequency: 10
+            },
+            report.trace[6]
+        );
+    }
+
+    #[test]
+    fn should_stop_following_at_limit() {
+        let report = Report::new(0, &lines("+10000000, -9999999"), 100, false).unwrap();
+
+        assert_eq!(100, report.followed);
+        assert!(report.stopped);
+        assert_eq!((0, 10_000_049), (report.min, report.max));
+        assert!(report.trace.is_empty());
+        assert_eq!(10_000_000, report.first_repeat.unwrap().pass);
+    }
+
+    #[test]
+    fn should_describe_report() {
+        let report = Report::new(0, &lines("+1, -2, +3, +1"), 1000, false).unwrap();
+
+        assert_eq!(
+            "Changes per pass: 4
+Drift per pass: 3
+Range: -1 to 4 over 6 changes
+First repeat: 2, before change 2 of pass 1 (both counting from 0)
+Visits:
+  1 time: 5 frequencies
+  2 times: 1 frequency
+",
+            report.to_string()
+        );
+
+        let report = Report::new(0, &lines("+1"), 3, false).unwrap();
+        assert!(report
+            .to_string()
+            .contains("over 3 changes, where following stopped\nFirst repeat: never"));
+    }
+
+    #[test]
+    fn should_count_passes_in_frequency_type() {
+        let lines = lines("+3000000000000000000000, -2999999999999999999999, -2");
+        let report = Report::new(BigInt::zero(), &lines, 1000, true).unwrap();
+        let repeat = report.first_repeat.unwrap();
+        let last = report.trace.last().unwrap();
+
+        // The repeat is given by the change about to be applied, so it follows the last step.
+        assert_eq!(BigInt::from(1), repeat.pass);
+        assert_eq!((&repeat.pass, repeat.index), (&last.pass, last.index + 1));
+        assert_eq!(repeat.frequency, last.frequency);
+    }
+
+    #[test]
+    fn should_write_trace_as_csv() {
+        let report = Report::new(0, &lines("+1, -1"), 1000, true).unwrap();
+        let mut csv = Vec::new();
+        report.write_trace(&mut csv).unwrap();
+
+        assert_eq!(
+            "step,pass,index,change,frequency\n1,0,0,+1,1\n2,0,1,-1,0\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+}
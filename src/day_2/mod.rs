@@ -1,9 +1,11 @@
-use crate::common;
+use crate::common::{self, AocError, Result};
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 use std::convert::TryInto;
-mod candidate;
+pub mod candidate;
 
-fn list_combinations(input: &str) -> candidate::Box {
+/// Records whether any letter appears exactly two or three times in the box ID.
+pub fn list_combinations(input: &str) -> candidate::Box {
     let mut has_two = false;
     let mut has_three = false;
 
@@ -26,13 +28,13 @@ fn list_combinations(input: &str) -> candidate::Box {
 }
 
 /// Returns whether the boxes match (by a one char difference) and whatever the first match index was.
-fn compare_boxes(box1: &str, box2: &str) -> (bool, i32) {
+pub fn compare_boxes(box1: &str, box2: &str) -> (bool, i32) {
     let mut count = 0;
     let mut index = -1;
     let box1_chars: Vec<char> = box1.chars().collect();
     let box2_chars: Vec<char> = box2.chars().collect();
 
-    if box1.len() != box2.len() {
+    if box1.len() != box2.len() || box1.is_empty() {
         return (false, index);
     }
 
@@ -48,7 +50,8 @@ fn compare_boxes(box1: &str, box2: &str) -> (bool, i32) {
     (count == 1, index)
 }
 
-fn calculate_checksum(input: Vec<String>) -> i32 {
+/// Multiplies the number of IDs with a doubled letter by the number with a tripled letter.
+pub fn calculate_checksum(input: Vec<String>) -> i32 {
     let mut twos = 0;
     let mut threes = 0;
 
@@ -62,39 +65,50 @@ fn calculate_checksum(input: Vec<String>) -> i32 {
     twos * threes
 }
 
-fn common_letters(input: Vec<String>) -> String {
-    let mut result = String::new();
-
-    'outer: for s1 in input.iter() {
+/// The letters shared by the two IDs that differ by exactly one character.
+pub fn common_letters(input: Vec<String>) -> Result<String> {
+    for s1 in input.iter() {
         for s2 in input.iter() {
             let (correct, index) = compare_boxes(s1, s2);
             if correct {
                 // Convert the index to a u32 for string indexing.
                 let index = index.try_into().unwrap();
-                result = format!("{}{}", &s1[..index], &s1[index + 1..]);
-                break 'outer;
+                return Ok(format!("{}{}", &s1[..index], &s1[index + 1..]));
             }
         }
     }
 
-    result
+    Err(AocError::no_solution(
+        "No two box IDs differ by exactly one character",
+    ))
 }
 
-pub fn part_1() {
-    let filename = "./inputs/day_2/input.txt";
-
-    if let Ok(lines) = common::read_lines(filename) {
-        let instructions = lines.map(|l| l.expect("Could not parse line")).collect();
-        println!("Day 2 - Part 1: {}", calculate_checksum(instructions));
+/// Checks a box ID is made of lowercase letters, as the comparisons index it by byte.
+pub fn parse_id(id: &str) -> Result<String> {
+    match id.chars().find(|c| !c.is_ascii_lowercase()) {
+        Some(letter) => {
+            let reason = format!("Box IDs are lowercase letters, not {:?}", letter);
+            Err(AocError::invalid(id, &reason))
+        }
+        None => Ok(id.to_string()),
     }
 }
 
-pub fn part_2() {
-    let filename = "./inputs/day_2/input.txt";
+pub struct InventoryManagementSystem;
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let instructions = lines.map(|l| l.expect("Could not parse line")).collect();
-        println!("Day 2 - Part 2: {}", common_letters(instructions));
+impl Solution for InventoryManagementSystem {
+    type Input = Vec<String>;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        common::parse_lines(&lines, parse_id)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(calculate_checksum(input.clone()).into())
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(common_letters(input.clone())?.into())
     }
 }
 
@@ -237,6 +251,27 @@ mod tests {
             String::from("wvxyz"),
         ];
         let answer = "fgij".to_string();
-        assert_eq!(answer, common_letters(input));
+        assert_eq!(answer, common_letters(input).unwrap());
+    }
+
+    #[test]
+    fn should_fail_without_common_letters() {
+        let input = vec![String::from("abcde"), String::from("fghij")];
+
+        assert!(common_letters(input).is_err());
+    }
+
+    #[test]
+    fn should_reject_invalid_ids() {
+        let ids = vec![String::from("abcde"), String::from("abcd")];
+        let invalid = vec![String::from("abcde"), String::from("ab-de")];
+
+        assert_eq!(ids, InventoryManagementSystem::parse(ids.clone()).unwrap());
+        assert_eq!(
+            "2: Box IDs are lowercase letters, not '-': \"ab-de\"",
+            InventoryManagementSystem::parse(invalid)
+                .unwrap_err()
+                .to_string()
+        );
     }
 }
@@ -1,66 +1,62 @@
-use crate::common;
+use crate::common::{self, AocError, Grid, Point2, Result};
+use crate::solution::{Answer, Solution};
 use rectangle::Rectangle;
-use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::{HashMap, HashSet};
-mod rectangle;
+pub mod rectangle;
 
-fn map_rectangles(input: Vec<String>) -> (HashMap<(u32, u32), u32>, u32) {
-    let mut result = HashMap::new();
-    let mut all_ids = HashSet::new();
-    let mut claimed_points = HashMap::new();
-    let mut intersected_ids = HashSet::new();
+/// Parses every claim, such as `#1 @ 1,3: 4x4`.
+pub fn parse_rectangles(input: &[String]) -> Result<Vec<Rectangle>> {
+    common::parse_lines(input, Rectangle::new)
+}
 
-    let rectangles: Vec<Rectangle> = input.iter().map(|s| Rectangle::new(s)).collect();
+/// Counts how many claims cover each square, and finds the one claim (if any) that overlaps no other.
+pub fn map_rectangles(rectangles: &[Rectangle]) -> (Grid<u32>, Option<u32>) {
+    let far_corner = rectangles
+        .iter()
+        .map(Rectangle::far_corner)
+        .fold(Point2::new(-1, -1), |far, c| {
+            Point2::new(far.x.max(c.x), far.y.max(c.y))
+        });
+    let mut fabric = Grid::covering(Point2::new(0, 0), far_corner, 0);
 
-    // Each rectangle can cover a series of points, we just collect these points and count
-    // how many times they're in our map.
     for r in rectangles.iter() {
-        // Collect all the IDs
-        all_ids.insert(r.id);
-
-        for x in r.x1..r.x1 + r.width {
-            for y in r.y1..r.y1 + r.height {
-                *result.entry((x, y)).or_insert(0) += 1;
-                // Check if this point has been claimed, and if it has add both ids to our list of intersections.
-                match claimed_points.entry((x, y)) {
-                    Vacant(entry) => {
-                        entry.insert(r.id);
-                    }
-                    Occupied(entry) => {
-                        intersected_ids.insert(*entry.get());
-                        intersected_ids.insert(r.id);
-                    }
-                }
-            }
+        for square in r.squares() {
+            fabric[square] += 1;
         }
     }
 
-    let lone_rect = all_ids.difference(&intersected_ids).next().unwrap();
+    // A claim overlaps nothing if it is the only one covering every one of its squares.
+    let lone_rect = rectangles
+        .iter()
+        .find(|r| r.squares().all(|square| fabric[square] == 1))
+        .map(|r| r.id);
 
-    (result, *lone_rect)
+    (fabric, lone_rect)
 }
 
-fn count_overlap(input: HashMap<(u32, u32), u32>) -> usize {
-    input.values().filter(|v| **v > 1).count()
+/// Counts the squares covered by two or more claims.
+pub fn count_overlap(fabric: &Grid<u32>) -> usize {
+    fabric.values().filter(|v| **v > 1).count()
 }
 
-pub fn part_1() {
-    let filename = "./inputs/day_3/input.txt";
+pub struct NoMatterHowYouSliceIt;
+
+impl Solution for NoMatterHowYouSliceIt {
+    type Input = Vec<Rectangle>;
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let rectangles = lines.map(|l| l.expect("Could not parse line")).collect();
-        let (result, _) = map_rectangles(rectangles);
-        println!("Day 3 - Part 1: {}", count_overlap(result));
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        parse_rectangles(&lines)
     }
-}
 
-pub fn part_2() {
-    let filename = "./inputs/day_3/input.txt";
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        let (fabric, _) = map_rectangles(input);
+        Ok(count_overlap(&fabric).into())
+    }
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let rectangles = lines.map(|l| l.expect("Could not parse line")).collect();
-        let (_, difference) = map_rectangles(rectangles);
-        println!("Day 3 - Part 2: #{}", difference);
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        let (_, difference) = map_rectangles(input);
+        difference
+            .map(Answer::from)
+            .ok_or_else(|| AocError::no_solution("Every claim overlaps another"))
     }
 }
 
@@ -75,13 +71,13 @@ mod tests {
             "#2 @ 3,1: 4x4".to_string(),
             "#3 @ 5,5: 2x2".to_string(),
         ];
-        let (result, _) = map_rectangles(recs);
+        let (result, _) = map_rectangles(&parse_rectangles(&recs).unwrap());
 
-        assert_eq!(32, result.len());
-        assert_eq!(&2, result.get(&(3, 3)).unwrap());
-        assert_eq!(&2, result.get(&(3, 4)).unwrap());
-        assert_eq!(&2, result.get(&(4, 3)).unwrap());
-        assert_eq!(&2, result.get(&(4, 4)).unwrap());
+        assert_eq!(32, result.values().filter(|v| **v > 0).count());
+        assert_eq!(2, result[Point2::new(3, 3)]);
+        assert_eq!(2, result[Point2::new(3, 4)]);
+        assert_eq!(2, result[Point2::new(4, 3)]);
+        assert_eq!(2, result[Point2::new(4, 4)]);
     }
 
     #[test]
@@ -91,9 +87,9 @@ mod tests {
             "#2 @ 3,1: 4x4".to_string(),
             "#3 @ 5,5: 2x2".to_string(),
         ];
-        let (_, difference) = map_rectangles(recs);
+        let (_, difference) = map_rectangles(&parse_rectangles(&recs).unwrap());
 
-        assert_eq!(3, difference);
+        assert_eq!(Some(3), difference);
     }
 
     #[test]
@@ -103,8 +99,27 @@ mod tests {
             "#2 @ 3,1: 4x4".to_string(),
             "#3 @ 5,5: 2x2".to_string(),
         ];
-        let (input, _) = map_rectangles(recs);
+        let (input, _) = map_rectangles(&parse_rectangles(&recs).unwrap());
+
+        assert_eq!(4, count_overlap(&input));
+    }
+
+    #[test]
+    fn should_report_line_of_invalid_claim() {
+        let recs = vec!["#1 @ 1,3: 4x4".to_string(), "#2 @ 3,1".to_string()];
+        let error = parse_rectangles(&recs).unwrap_err();
+
+        assert_eq!(
+            "2: Expected a claim like #1 @ 2,3: 4x5: \"#2 @ 3,1\"",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn should_find_no_lone_rectangle() {
+        let recs = vec!["#1 @ 1,3: 4x4".to_string(), "#2 @ 3,1: 4x4".to_string()];
+        let (_, difference) = map_rectangles(&parse_rectangles(&recs).unwrap());
 
-        assert_eq!(4, count_overlap(input));
+        assert_eq!(None, difference);
     }
 }
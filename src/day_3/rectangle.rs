@@ -1,24 +1,52 @@
+use crate::common::{AocError, Pattern, Point2, Result};
+use std::sync::OnceLock;
+
+static CLAIM: OnceLock<Pattern> = OnceLock::new();
+
+#[derive(Debug)]
 pub struct Rectangle {
     pub id: u32,
-    pub x1: u32,
-    pub y1: u32,
-    pub width: u32,
-    pub height: u32,
+    /// The top-left square of the claim.
+    pub corner: Point2,
+    /// The width and height of the claim, as `x` and `y`.
+    pub size: Point2,
 }
 
 impl Rectangle {
-    pub fn new(input: &str) -> Rectangle {
-        let r = input
-            .split(|c| c == ' ' || c == '#' || c == '@' || c == ',' || c == ':' || c == 'x')
-            .filter_map(|c| c.parse::<u32>().ok())
-            .collect::<Vec<_>>();
-        Rectangle {
-            id: r[0],
-            x1: r[1],
-            y1: r[2],
-            width: r[3],
-            height: r[4],
+    pub fn new(input: &str) -> Result<Rectangle> {
+        let fields = CLAIM
+            .get_or_init(|| Pattern::new("#{id} @ {x},{y}: {width}x{height}"))
+            .parse(input)
+            .map_err(|_| AocError::invalid(input, "Expected a claim like #1 @ 2,3: 4x5"))?;
+
+        let corner = Point2::new(fields.parse("x")?, fields.parse("y")?);
+        if corner.x < 0 || corner.y < 0 {
+            return Err(AocError::invalid(input, "Claim starts off the fabric"));
+        }
+        let size = Point2::new(fields.parse("width")?, fields.parse("height")?);
+        if size.x < 0 || size.y < 0 {
+            return Err(AocError::invalid(input, "Claim has a negative size"));
+        }
+        if corner.x.checked_add(size.x).is_none() || corner.y.checked_add(size.y).is_none() {
+            return Err(AocError::invalid(input, "Claim is too large to place"));
         }
+
+        Ok(Rectangle {
+            id: fields.parse("id")?,
+            corner,
+            size,
+        })
+    }
+
+    /// The bottom-right square of the claim. Only meaningful if the claim covers any squares.
+    pub fn far_corner(&self) -> Point2 {
+        self.corner + self.size - Point2::new(1, 1)
+    }
+
+    /// Every square the claim covers, row by row.
+    pub fn squares(&self) -> impl Iterator<Item = Point2> + '_ {
+        let Point2 { x, y } = self.corner;
+        (y..y + self.size.y).flat_map(move |y| (x..x + self.size.x).map(move |x| Point2::new(x, y)))
     }
 }
 
@@ -29,12 +57,48 @@ mod tests {
     #[test]
     fn should_create_rectangle() {
         let input = "#123 @ 3,2: 5x4";
-        let result = Rectangle::new(input);
+        let result = Rectangle::new(input).unwrap();
 
         assert_eq!(123, result.id);
-        assert_eq!(3, result.x1);
-        assert_eq!(2, result.y1);
-        assert_eq!(5, result.width);
-        assert_eq!(4, result.height);
+        assert_eq!(Point2::new(3, 2), result.corner);
+        assert_eq!(Point2::new(5, 4), result.size);
+        assert_eq!(Point2::new(7, 5), result.far_corner());
+    }
+
+    #[test]
+    fn should_list_covered_squares() {
+        let result = Rectangle::new("#1 @ 3,2: 2x2").unwrap();
+
+        assert_eq!(
+            vec![
+                Point2::new(3, 2),
+                Point2::new(4, 2),
+                Point2::new(3, 3),
+                Point2::new(4, 3)
+            ],
+            result.squares().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_reject_malformed_claims() {
+        assert!(Rectangle::new("#123 @ 3,2: 5").is_err());
+        assert!(Rectangle::new("#123 @ 3,2: 5x4x1").is_err());
+        assert!(Rectangle::new("#123 @ 3,two: 5x4").is_err());
+        assert!(Rectangle::new("#123 @ -3,2: 5x4").is_err());
+        assert!(Rectangle::new("#123 @ 3,2: -5x4").is_err());
+        assert!(Rectangle::new("").is_err());
+    }
+
+    #[test]
+    fn should_reject_claims_past_largest_square() {
+        assert_eq!(
+            "Claim is too large to place: \"#1 @ 2147483000,0: 1000x1\"",
+            Rectangle::new("#1 @ 2147483000,0: 1000x1")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(Rectangle::new("#1 @ 0,2147483000: 1x1000").is_err());
+        assert!(Rectangle::new("#1 @ 2147482647,0: 1000x1").is_ok());
     }
 }
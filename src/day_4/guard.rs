@@ -1,5 +1,10 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use crate::common::{AocError, Pattern, Result};
+use chrono::NaiveDateTime;
 use std::cmp;
+use std::sync::OnceLock;
+
+static RECORD: OnceLock<Pattern> = OnceLock::new();
+static SHIFT: OnceLock<Pattern> = OnceLock::new();
 
 #[derive(Eq, Debug)]
 pub struct Schedule {
@@ -8,16 +13,41 @@ pub struct Schedule {
 }
 
 impl Schedule {
-    pub fn new(line: String) -> Schedule {
-        let date_str = &line[1..17];
-        let message = (&line[19..]).to_string();
+    pub fn new(line: String) -> Result<Schedule> {
+        let fields = RECORD
+            .get_or_init(|| Pattern::new("[{time}] {message}"))
+            .parse(&line)
+            .map_err(|_| {
+                AocError::invalid(&line, "Expected a record like [1518-11-01 00:00] message")
+            })?;
 
-        let datetime = match NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M") {
-            Ok(datetime) => datetime,
-            Err(_) => NaiveDate::from_ymd(1518, 1, 1).and_hms(0, 0, 0),
-        };
+        let datetime = NaiveDateTime::parse_from_str(fields.get("time"), "%Y-%m-%d %H:%M")
+            .map_err(|_| AocError::invalid(&line, "Record has an invalid timestamp"))?;
+        let message = fields.get("message").to_string();
 
-        Schedule { datetime, message }
+        let schedule = Schedule { datetime, message };
+        if schedule.message != "falls asleep"
+            && schedule.message != "wakes up"
+            && schedule.guard()?.is_none()
+        {
+            return Err(AocError::invalid(&line, "Unknown record message"));
+        }
+
+        Ok(schedule)
+    }
+
+    /// The ID of the guard, if this record is the start of a shift.
+    pub fn guard(&self) -> Result<Option<u32>> {
+        if !self.message.contains("Guard") {
+            return Ok(None);
+        }
+
+        SHIFT
+            .get_or_init(|| Pattern::new("Guard #{id} begins shift"))
+            .parse(&self.message)
+            .and_then(|fields| fields.parse("id"))
+            .map(Some)
+            .map_err(|_| AocError::invalid(&self.message, "Expected Guard #<ID> begins shift"))
     }
 
     pub fn datetime(&self) -> &NaiveDateTime {
@@ -29,6 +59,7 @@ impl Schedule {
     }
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Schedule {
     fn partial_cmp(&self, other: &Schedule) -> Option<cmp::Ordering> {
         Some(other.cmp(self))
@@ -60,15 +91,33 @@ mod tests {
             message: "falls asleep".to_string(),
         };
 
-        assert_eq!(result, Schedule::new(line));
+        assert_eq!(result, Schedule::new(line).unwrap());
+    }
+
+    #[test]
+    fn should_reject_invalid_schedules() {
+        assert!(Schedule::new("".to_string()).is_err());
+        assert!(Schedule::new("[1518-06-27 00:21]".to_string()).is_err());
+        assert!(Schedule::new("[1518-13-27 00:21] falls asleep".to_string()).is_err());
+        assert!(Schedule::new("[1518-06-27 00:21] sings".to_string()).is_err());
+        assert!(Schedule::new("[1518-06-27 00:21] Guard #x begins shift".to_string()).is_err());
+    }
+
+    #[test]
+    fn should_read_guard_id() {
+        let guard = Schedule::new("[1518-11-10 23:52] Guard #881 begins shift".to_string());
+        let asleep = Schedule::new("[1518-06-27 00:21] falls asleep".to_string());
+
+        assert_eq!(Some(881), guard.unwrap().guard().unwrap());
+        assert_eq!(None, asleep.unwrap().guard().unwrap());
     }
 
     #[test]
     fn should_order_schedules() {
         let mut input = vec![
-            Schedule::new("[1518-06-27 00:21] falls asleep".to_string()),
-            Schedule::new("[1518-06-05 00:46] falls asleep".to_string()),
-            Schedule::new("[1518-11-10 23:52] Guard #881 begins shift".to_string()),
+            Schedule::new("[1518-06-27 00:21] falls asleep".to_string()).unwrap(),
+            Schedule::new("[1518-06-05 00:46] falls asleep".to_string()).unwrap(),
+            Schedule::new("[1518-11-10 23:52] Guard #881 begins shift".to_string()).unwrap(),
         ];
         let expected = vec![
             Schedule {
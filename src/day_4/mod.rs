@@ -1,18 +1,20 @@
-use crate::common;
+use crate::common::{self, Result};
+use crate::solution::{Answer, Solution};
 use chrono::Timelike;
 use guard::Schedule;
 use std::collections::HashMap;
-mod guard;
+pub mod guard;
 
-fn order_records(input: Vec<String>) -> Vec<Schedule> {
-    let mut schedules: Vec<Schedule> = input.iter().map(|s| Schedule::new(s.to_string())).collect();
+/// Parses the records and sorts them into chronological order.
+pub fn order_records(input: Vec<String>) -> Result<Vec<Schedule>> {
+    let mut schedules = common::parse_lines(&input, |s| Schedule::new(s.to_string()))?;
     schedules.sort();
     schedules.reverse();
-    schedules
+    Ok(schedules)
 }
 
 // Key: Guard ID, Value: (Sleep length, Most common minute)
-fn determine_shifts(input: Vec<Schedule>) -> HashMap<u32, (u32, u32, u32)> {
+pub fn determine_shifts(input: Vec<Schedule>) -> Result<HashMap<u32, (u32, u32, u32)>> {
     // Map the Guard ID to all the minutes he's asleep, and how many times he is asleep at that minute.
     let mut minute_tracker: HashMap<u32, HashMap<u32, u32>> = HashMap::new();
     let mut start_min = 0;
@@ -23,12 +25,9 @@ fn determine_shifts(input: Vec<Schedule>) -> HashMap<u32, (u32, u32, u32)> {
         let msg = schedule.message();
         let time = schedule.datetime().minute();
 
-        if msg.contains("Guard") {
-            let start_idx = msg.find('#').unwrap();
-            let end_idx = msg.find("begins").unwrap();
-
-            last_id = (&msg[start_idx + 1..end_idx - 1]).parse::<u32>().unwrap();
-            minute_tracker.entry(last_id).or_insert_with(HashMap::new);
+        if let Some(id) = schedule.guard()? {
+            last_id = id;
+            minute_tracker.entry(last_id).or_default();
             continue;
         }
 
@@ -59,10 +58,11 @@ fn determine_shifts(input: Vec<Schedule>) -> HashMap<u32, (u32, u32, u32)> {
         result.insert(*key, (total_minutes, *most_common_min, *common_min_count));
     }
 
-    result
+    Ok(result)
 }
 
-fn calculate_strategy_1(input: HashMap<u32, (u32, u32, u32)>) -> u32 {
+/// The ID of the guard asleep the longest, multiplied by the minute they are most often asleep.
+pub fn calculate_strategy_1(input: HashMap<u32, (u32, u32, u32)>) -> u32 {
     let mut max_len = 0;
     let mut current_guard = 0;
     let mut current_common = 0;
@@ -78,7 +78,8 @@ fn calculate_strategy_1(input: HashMap<u32, (u32, u32, u32)>) -> u32 {
     current_common * current_guard
 }
 
-fn calculate_strategy_2(input: HashMap<u32, (u32, u32, u32)>) -> u32 {
+/// The ID of the guard most frequently asleep on the same minute, multiplied by that minute.
+pub fn calculate_strategy_2(input: HashMap<u32, (u32, u32, u32)>) -> u32 {
     let mut max_count = 0;
     let mut current_guard = 0;
     let mut current_min = 0;
@@ -94,27 +95,22 @@ fn calculate_strategy_2(input: HashMap<u32, (u32, u32, u32)>) -> u32 {
     current_min * current_guard
 }
 
-pub fn part_1() {
-    let filename = "./inputs/day_4/input.txt";
+pub struct ReposeRecord;
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let input = lines.map(|l| l.expect("Could not parse line")).collect();
-        let sorted = order_records(input);
-        let shifts = determine_shifts(sorted);
-        let result = calculate_strategy_1(shifts);
-        println!("Day 4 - Part 1: {}", result);
+impl Solution for ReposeRecord {
+    type Input = HashMap<u32, (u32, u32, u32)>;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        let sorted = order_records(lines)?;
+        determine_shifts(sorted)
     }
-}
 
-pub fn part_2() {
-    let filename = "./inputs/day_4/input.txt";
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(calculate_strategy_1(input.clone()).into())
+    }
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let input = lines.map(|l| l.expect("Could not parse line")).collect();
-        let sorted = order_records(input);
-        let shifts = determine_shifts(sorted);
-        let result = calculate_strategy_2(shifts);
-        println!("Day 4 - Part 2: {}", result);
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(calculate_strategy_2(input.clone()).into())
     }
 }
 
@@ -131,7 +127,7 @@ mod tests {
             "[1518-11-10 23:52] Guard #881 begins shift".to_string(),
         ];
 
-        let result = order_records(input);
+        let result = order_records(input).unwrap();
 
         assert_eq!(
             &NaiveDate::from_ymd(1518, 6, 5).and_hms(0, 46, 0),
@@ -149,6 +145,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_report_line_of_invalid_record() {
+        let input = vec![
+            "[1518-06-27 00:21] falls asleep".to_string(),
+            "[1518-06-05 00:46] falls asleep".to_string(),
+            "[1518-11-10 23:52] Guard #x begins shift".to_string(),
+        ];
+
+        let error = order_records(input).unwrap_err();
+
+        assert_eq!(
+            "3: Expected Guard #<ID> begins shift: \"Guard #x begins shift\"",
+            error.to_string()
+        );
+    }
+
     #[test]
     fn should_separate_guard_shifts() {
         let input = vec![
@@ -171,8 +183,8 @@ mod tests {
             "[1518-11-05 00:55] wakes up".to_string(),
         ];
 
-        let sorted = order_records(input);
-        let shifts = determine_shifts(sorted);
+        let sorted = order_records(input).unwrap();
+        let shifts = determine_shifts(sorted).unwrap();
 
         let mut expected: HashMap<u32, (u32, u32, u32)> = HashMap::new();
         expected.insert(10, (50, 24, 2));
@@ -203,8 +215,8 @@ mod tests {
             "[1518-11-05 00:55] wakes up".to_string(),
         ];
 
-        let sorted = order_records(input);
-        let shifts = determine_shifts(sorted);
+        let sorted = order_records(input).unwrap();
+        let shifts = determine_shifts(sorted).unwrap();
         let result = calculate_strategy_1(shifts);
 
         assert_eq!(240, result);
@@ -232,8 +244,8 @@ mod tests {
             "[1518-11-05 00:55] wakes up".to_string(),
         ];
 
-        let sorted = order_records(input);
-        let shifts = determine_shifts(sorted);
+        let sorted = order_records(input).unwrap();
+        let shifts = determine_shifts(sorted).unwrap();
         let result = calculate_strategy_2(shifts);
 
         assert_eq!(4455, result);
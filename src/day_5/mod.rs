@@ -1,16 +1,17 @@
-use crate::common;
+use crate::common::{self, AocError, Result};
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
-fn check_unit(unit: &str) -> bool {
+/// Whether a pair of units are the same type with opposite polarity, and so react.
+pub fn check_unit(unit: &str) -> bool {
     let mut chars: Vec<char> = unit.chars().collect();
     chars.sort_unstable();
 
-    chars[0].is_uppercase()
-        && chars[1].is_lowercase()
-        && (chars[0].to_ascii_lowercase() == chars[1].to_ascii_lowercase())
+    chars[0].is_uppercase() && chars[1].is_lowercase() && chars[0].eq_ignore_ascii_case(&chars[1])
 }
 
-fn process_polymer(polymer: &str) -> String {
+/// The polymer left once every reaction has happened.
+pub fn process_polymer(polymer: &str) -> String {
     let mut buffer: Vec<char> = Vec::new();
 
     for c in polymer.chars() {
@@ -24,7 +25,8 @@ fn process_polymer(polymer: &str) -> String {
     buffer.iter().collect()
 }
 
-fn shortest_possible_polymer(polymer: &str) -> usize {
+/// The length of the shortest polymer possible after removing every unit of a single type.
+pub fn shortest_possible_polymer(polymer: &str) -> usize {
     let current = process_polymer(polymer);
     let lowercase = current.to_ascii_lowercase();
 
@@ -53,25 +55,29 @@ fn shortest_possible_polymer(polymer: &str) -> usize {
     lowest
 }
 
-pub fn part_1() {
-    let filename = "./inputs/day_5/input.txt";
+pub struct AlchemicalReduction;
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let polymer = lines
-            .map(|l| l.expect("Could not parse line"))
-            .collect::<String>();
-        println!("Day 5 - Part 1: {}", process_polymer(&polymer).len());
+impl Solution for AlchemicalReduction {
+    type Input = String;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        common::parse_lines(&lines, |line| {
+            if line.chars().all(|c| c.is_ascii_alphabetic()) {
+                Ok(())
+            } else {
+                Err(AocError::invalid(line, "Polymer units must be letters"))
+            }
+        })?;
+
+        Ok(lines.concat())
     }
-}
 
-pub fn part_2() {
-    let filename = "./inputs/day_5/input.txt";
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        Ok(process_polymer(input).len().into())
+    }
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let polymer = lines
-            .map(|l| l.expect("Could not parse line"))
-            .collect::<String>();
-        println!("Day 5 - Part 2: {}", shortest_possible_polymer(&polymer));
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        Ok(shortest_possible_polymer(input).into())
     }
 }
 
@@ -119,4 +125,11 @@ mod tests {
         let input = "dabAcCaCBAcCcaDA";
         assert_eq!(4, shortest_possible_polymer(input));
     }
+
+    #[test]
+    fn should_reject_non_letter_units() {
+        let lines = vec!["dabAcC".to_string(), "aC1BA".to_string()];
+
+        assert!(AlchemicalReduction::parse(lines).is_err());
+    }
 }
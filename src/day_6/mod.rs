@@ -1,147 +1,139 @@
-use crate::common;
+use crate::common::{self, AocError, Grid, Pattern, Point2, Result};
+use crate::solution::{Answer, Solution};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
-fn calculate_distance(p1: &(i32, i32), p2: &(i32, i32)) -> i32 {
-    (p1.0 - p2.0).abs() + (p1.1 - p2.1).abs()
-}
+static COORDINATE: OnceLock<Pattern> = OnceLock::new();
 
-fn calculate_map_bounds(map: &[(i32, i32)]) -> (i32, i32) {
-    let x_max = *map
-        .iter()
-        .max_by(|a, b| a.0.cmp(&b.0))
-        .map(|(x, _)| x)
-        .unwrap();
-    let y_max = *map
-        .iter()
-        .max_by(|a, b| a.1.cmp(&b.1))
-        .map(|(_, y)| y)
-        .unwrap();
-
-    (x_max, y_max)
-}
+/// The smallest and largest x and y of any coordinate, or `None` if there are none.
+pub fn calculate_map_bounds(map: &[Point2]) -> Option<(Point2, Point2)> {
+    let xs = map.iter().map(|p| p.x);
+    let ys = map.iter().map(|p| p.y);
 
-fn determine_closest_points(input: &[(i32, i32)]) -> HashMap<usize, Vec<(i32, i32)>> {
-    let (x_max, y_max) = calculate_map_bounds(input);
-
-    let mut result: HashMap<usize, Vec<(i32, i32)>> = HashMap::new();
-
-    for x in 0..=x_max {
-        for y in 0..=y_max {
-            let mut lowest_distance = 1000;
-            let mut input_index = 0;
-            let mut all_equal = false;
-
-            for (i, c) in input.iter().enumerate() {
-                let current_distance = calculate_distance(c, &(x, y));
-                match current_distance.cmp(&lowest_distance) {
-                    Ordering::Equal => all_equal = true,
-                    Ordering::Greater => continue,
-                    Ordering::Less => {
-                        all_equal = false;
-                        input_index = i;
-                        lowest_distance = current_distance;
-                    }
-                }
-            }
+    Some((
+        Point2::new(xs.clone().min()?, ys.clone().min()?),
+        Point2::new(xs.max()?, ys.max()?),
+    ))
+}
 
-            if all_equal {
-                continue;
-            } else {
-                result
-                    .entry(input_index)
-                    .or_insert_with(Vec::new)
-                    .push((x, y))
-            }
+/// Every location within the coordinates' bounds, widened by `padding` on each side, with `f`
+/// applied to each. Empty if there are no coordinates.
+fn map_grid<T, F: FnMut(Point2) -> T>(map: &[Point2], padding: i32, f: F) -> Grid<T> {
+    match calculate_map_bounds(map) {
+        Some((min, max)) => {
+            let min = min - Point2::new(padding, padding);
+            let size = max - min + Point2::new(padding + 1, padding + 1);
+            Grid::from_fn(min, size.x as usize, size.y as usize, f)
         }
+        None => Grid::from_fn(Point2::new(0, 0), 0, 0, f),
     }
-
-    result
 }
 
-fn remove_edge_locations(
-    map: &[(i32, i32)],
-    input: HashMap<usize, Vec<(i32, i32)>>,
-) -> HashMap<usize, Vec<(i32, i32)>> {
-    let (x_max, y_max) = calculate_map_bounds(map);
-    let mut result = input.clone();
-
-    for (coord, point_list) in input.iter() {
-        for point in point_list.iter() {
-            if point.0 == 0 || point.1 == 0 || point.0 == x_max || point.1 == y_max {
-                result.remove_entry(coord);
-                break;
+/// The index of the coordinate closest to a location, or `None` if two or more are equally close.
+fn closest_point(input: &[Point2], location: Point2) -> Option<usize> {
+    let mut closest = None;
+    let mut lowest_distance = i32::MAX;
+
+    for (i, c) in input.iter().enumerate() {
+        let current_distance = c.manhattan(location);
+        match current_distance.cmp(&lowest_distance) {
+            Ordering::Equal => closest = None,
+            Ordering::Greater => continue,
+            Ordering::Less => {
+                closest = Some(i);
+                lowest_distance = current_distance;
             }
         }
     }
 
-    result
-}
-
-fn parse_point(point: &str) -> (i32, i32) {
-    let vals: Vec<&str> = point.split(", ").collect();
-    let x: i32 = vals[0].parse().unwrap();
-    let y: i32 = vals[1].parse().unwrap();
-
-    (x, y)
+    closest
 }
 
-fn sum_distances(point: &(i32, i32), map: &[(i32, i32)]) -> i32 {
-    map.iter()
-        .fold(0, |acc, c| acc + calculate_distance(c, point))
+/// Marks each location with the index of the coordinate closest to it, if there is just one.
+pub fn determine_closest_points(input: &[Point2]) -> Grid<Option<usize>> {
+    map_grid(input, 0, |location| closest_point(input, location))
 }
 
-fn determine_region(map: &[(i32, i32)], max_distance: i32) -> Vec<(i32, i32)> {
-    let (x_max, y_max) = calculate_map_bounds(map);
-
-    let mut result: Vec<(i32, i32)> = Vec::new();
-
-    for x in 0..=x_max {
-        for y in 0..=y_max {
-            let dist = sum_distances(&(x, y), map);
+/// The size of each coordinate's area, leaving out those that reach the edge of the map, since those are infinite.
+pub fn finite_areas(closest: &Grid<Option<usize>>) -> HashMap<usize, usize> {
+    let mut result = HashMap::new();
+    for (_, owner) in closest.iter() {
+        if let Some(owner) = owner {
+            *result.entry(*owner).or_insert(0) += 1;
+        }
+    }
 
-            if dist < max_distance {
-                result.push((x, y));
-            }
+    for (point, owner) in closest.iter() {
+        if let (true, Some(owner)) = (closest.is_edge(point), owner) {
+            result.remove(owner);
         }
     }
 
     result
 }
 
-pub fn part_1() {
-    let filename = "./inputs/day_6/input.txt";
+/// Parses a coordinate such as `46, 246`.
+pub fn parse_point(point: &str) -> Result<Point2> {
+    let fields = COORDINATE
+        .get_or_init(|| Pattern::new("{x},{y}"))
+        .parse(point)
+        .map_err(|_| AocError::invalid(point, "Expected a coordinate like 46, 246"))?;
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let points: Vec<(i32, i32)> = lines
-            .map(|l| l.expect("Could not parse line"))
-            .map(|l| parse_point(&l))
-            .collect();
+    Ok(Point2::new(fields.parse("x")?, fields.parse("y")?))
+}
 
-        let closest_points = determine_closest_points(&points);
-        let internal_areas = remove_edge_locations(&points, closest_points);
-        let largest_area = internal_areas
-            .iter()
-            .max_by(|a, b| a.1.len().cmp(&b.1.len()))
-            .map(|(_, v)| v.len())
-            .unwrap();
+/// The total distance from a location to every coordinate.
+pub fn sum_distances(point: &Point2, map: &[Point2]) -> i32 {
+    map.iter().map(|c| c.manhattan(*point)).sum()
+}
 
-        println!("Day 6 - Part 1: {}", largest_area);
-    }
+/// Every location with a total distance to all coordinates below `max_distance`. A location `d`
+/// steps outside the bounds is at least `d` from every coordinate, so the region reaches no further
+/// out than `max_distance` shared between them.
+pub fn determine_region(map: &[Point2], max_distance: i32) -> Vec<Point2> {
+    let padding = match map.len() {
+        0 => 0,
+        n => (max_distance - 1).max(0) / n as i32,
+    };
+
+    map_grid(map, padding, |location| {
+        sum_distances(&location, map) < max_distance
+    })
+    .iter()
+    .filter(|(_, inside)| **inside)
+    .map(|(location, _)| location)
+    .collect()
 }
 
-pub fn part_2() {
-    let filename = "./inputs/day_6/input.txt";
+pub struct ChronalCoordinates;
+
+impl Solution for ChronalCoordinates {
+    type Input = Vec<Point2>;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        common::parse_lines(&lines, parse_point)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Answer> {
+        if input.is_empty() {
+            return Err(AocError::no_solution("There are no coordinates"));
+        }
 
-    if let Ok(lines) = common::read_lines(filename) {
-        let points: Vec<(i32, i32)> = lines
-            .map(|l| l.expect("Could not parse line"))
-            .map(|l| parse_point(&l))
-            .collect();
+        let closest_points = determine_closest_points(input);
+        finite_areas(&closest_points)
+            .values()
+            .max()
+            .map(|size| (*size).into())
+            .ok_or_else(|| AocError::no_solution("Every area is infinite"))
+    }
 
-        let region_size = determine_region(&points, 10000).len();
+    fn part_2(input: &Self::Input) -> Result<Answer> {
+        if input.is_empty() {
+            return Err(AocError::no_solution("There are no coordinates"));
+        }
 
-        println!("Day 6 - Part 2: {}", region_size);
+        Ok(determine_region(input, 10000).len().into())
     }
 }
 
@@ -149,34 +141,44 @@ pub fn part_2() {
 mod tests {
     use super::*;
 
-    #[test]
-    fn should_calculate_manhatten_distance() {
-        let point_1 = (1, 1);
-        let point_2 = (5, 0);
-
-        assert_eq!(5, calculate_distance(&point_1, &point_2));
+    fn points(coordinates: &[(i32, i32)]) -> Vec<Point2> {
+        coordinates.iter().map(|c| Point2::from(*c)).collect()
+    }
 
-        let point_1 = (5, 5);
+    #[test]
+    fn should_calculate_map_bounds() {
+        let input = points(&[(1, 6), (8, 3), (3, 9)]);
+
+        assert_eq!(
+            Some((Point2::new(1, 3), Point2::new(8, 9))),
+            calculate_map_bounds(&input)
+        );
+        assert_eq!(None, calculate_map_bounds(&[]));
+    }
 
-        assert_eq!(5, calculate_distance(&point_1, &point_2));
+    #[test]
+    fn should_map_nothing_without_coordinates() {
+        assert_eq!(0, determine_closest_points(&[]).values().count());
+        assert!(determine_region(&[], 10000).is_empty());
     }
 
     #[test]
     fn should_determine_closest_points() {
-        let input = vec![(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+        let input = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
 
         let result = determine_closest_points(&input);
 
-        assert_eq!(9, result.get(&3).unwrap().len());
-        assert_eq!(17, result.get(&4).unwrap().len());
+        assert_eq!(9, result.values().filter(|c| **c == Some(3)).count());
+        assert_eq!(17, result.values().filter(|c| **c == Some(4)).count());
+        assert_eq!(None, result[Point2::new(5, 1)]);
     }
 
     #[test]
-    fn should_remove_edge_locations() {
-        let input = vec![(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+    fn should_find_finite_areas() {
+        let input = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
         let points = determine_closest_points(&input);
 
-        let result = remove_edge_locations(&input, points);
+        let result = finite_areas(&points);
 
         assert!(!result.contains_key(&0));
         assert!(!result.contains_key(&1));
@@ -190,22 +192,52 @@ mod tests {
     fn should_read_line_to_point() {
         let input = "46, 246";
 
-        assert_eq!((46, 246), parse_point(input));
+        assert_eq!(Point2::new(46, 246), parse_point(input).unwrap());
+    }
+
+    #[test]
+    fn should_reject_invalid_points() {
+        assert!(parse_point("46").is_err());
+        assert!(parse_point("46, 246, 1").is_err());
+        assert!(parse_point("46, x").is_err());
+    }
+
+    #[test]
+    fn should_fail_when_every_area_is_infinite() {
+        let input = points(&[(0, 0), (2, 2)]);
+
+        assert!(ChronalCoordinates::part_1(&input).is_err());
     }
 
     #[test]
     fn should_sum_distances() {
-        let point = (4, 3);
-        let map = vec![(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+        let point = Point2::new(4, 3);
+        let map = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
 
         assert_eq!(30, sum_distances(&point, &map));
     }
 
     #[test]
     fn should_find_points_within_region() {
-        let map = vec![(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)];
+        let map = points(&[(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]);
         let maximum = 32;
 
         assert_eq!(16, determine_region(&map, maximum).len());
     }
+
+    #[test]
+    fn should_find_region_beyond_coordinate_bounds() {
+        let map = points(&[(5, 5), (6, 6)]);
+        let inside = |x, y| sum_distances(&Point2::new(x, y), &map) < 20;
+        let expected = (-20..=30)
+            .flat_map(|x| (-20..=30).map(move |y| (x, y)))
+            .filter(|(x, y)| inside(*x, *y))
+            .count();
+
+        let region = determine_region(&map, 20);
+
+        assert_eq!(expected, region.len());
+        assert!(region.contains(&Point2::new(1, 1)));
+        assert!(region.contains(&Point2::new(10, 10)));
+    }
 }
@@ -0,0 +1,134 @@
+//! Worked examples from the puzzle text, kept on disk so anyone can add a case without touching code.
+//!
+//! Each day's examples live in `inputs/day_N/examples/`. An example is a puzzle input, such as
+//! `larger.txt`, alongside a sidecar with the same name, `larger.toml`, holding its answers:
+//!
+//! ```toml
+//! part_1 = 17
+//! part_2 = "fgij"
+//! ```
+//!
+//! Either part can be left out if the example doesn't cover it.
+
+use crate::answers::{Answers, Verdict};
+use crate::common::{AocError, Result};
+use crate::runner::{self, Limits};
+use crate::solution::{Answer, Day};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub struct Example {
+    pub day: Day,
+    pub name: String,
+    pub input_path: String,
+    pub answers: Answers,
+}
+
+/// The outcome of checking one part of an example.
+pub struct Check {
+    pub part: u8,
+    pub result: Result<Verdict>,
+    pub answer: Option<Answer>,
+}
+
+/// Finds every example for the day, sorted by name. A day with no examples directory has no examples.
+pub fn find(day: &Day) -> Result<Vec<Example>> {
+    let dir = day.examples_path();
+    let to_error = |source| AocError::Io {
+        file: dir.clone(),
+        source,
+    };
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(to_error(e)),
+    };
+
+    let mut names = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(to_error)?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("txt") {
+            if let Some(name) = path.file_stem().and_then(|n| n.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let input_path = format!("{}/{}.txt", dir, name);
+            let sidecar = format!("{}/{}.toml", dir, name);
+            if !Path::new(&sidecar).exists() {
+                return Err(
+                    AocError::invalid(&input_path, "Example has no answers sidecar")
+                        .in_file(&sidecar),
+                );
+            }
+
+            Ok(Example {
+                day: *day,
+                name,
+                input_path,
+                answers: Answers::load_for_day(&sidecar, day.number)?,
+            })
+        })
+        .collect()
+}
+
+impl Example {
+    /// Solves each part the sidecar has an answer for, and compares it.
+    pub fn check(&self) -> Result<Vec<Check>> {
+        let parts = self.answers.parts(self.day.number);
+        let run = runner::solve_day(&self.day, &parts, &self.input_path, &Limits::default())?;
+
+        Ok(run
+            .parts
+            .into_iter()
+            .map(|p| match p.result {
+                Ok(result) => Check {
+                    part: p.part,
+                    result: Ok(self.answers.check(&result)),
+                    answer: Some(result.answer),
+                },
+                Err(e) => Check {
+                    part: p.part,
+                    result: Err(e),
+                    answer: None,
+                },
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    #[test]
+    fn should_find_examples_with_answers() {
+        let day = solution::find(5).unwrap();
+        let examples = find(&day).unwrap();
+
+        assert!(!examples.is_empty());
+        assert!(examples.iter().all(|e| !e.answers.parts(5).is_empty()));
+    }
+
+    #[test]
+    fn should_check_example() {
+        let day = solution::find(5).unwrap();
+        let example = find(&day)
+            .unwrap()
+            .into_iter()
+            .find(|e| e.name == "polymer")
+            .unwrap();
+        let checks = example.check().unwrap();
+
+        assert_eq!(2, checks.len());
+        assert!(checks.iter().all(|c| matches!(c.result, Ok(Verdict::Pass))));
+    }
+}
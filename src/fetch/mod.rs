@@ -0,0 +1,208 @@
+//! Downloads puzzle inputs, so they don't have to be copied in by hand.
+//!
+//! The session cookie and website are read from `aoc.toml`, which should never be committed:
+//!
+//! ```toml
+//! session = "53616c7465645f5f..."
+//! base_url = "https://adventofcode.com"
+//! year = 2018
+//! ```
+//!
+//! The `AOC_SESSION` and `AOC_BASE_URL` environment variables take priority over the file.
+
+use crate::answers;
+use crate::common::{self, AocError, Result};
+use crate::http;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+#[derive(PartialEq, Debug)]
+pub struct Config {
+    pub base_url: String,
+    pub year: u16,
+    pub session: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2018,
+            session: None,
+        }
+    }
+}
+
+/// Whether an input had to be downloaded.
+#[derive(PartialEq, Debug)]
+pub enum Fetched {
+    Downloaded,
+    Cached,
+}
+
+impl Config {
+    pub fn parse(lines: &[String]) -> Result<Config> {
+        let mut config = Config::default();
+
+        for (i, line) in lines.iter().enumerate() {
+            let at_line = |e: AocError| e.at_line(i + 1);
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = match trimmed.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(at_line(AocError::invalid(line, "Expected key = value"))),
+            };
+            match key {
+                "session" => {
+                    config.session = Some(answers::parse_string(line, value).map_err(at_line)?)
+                }
+                "base_url" => {
+                    config.base_url = answers::parse_string(line, value).map_err(at_line)?
+                }
+                "year" => {
+                    config.year = value
+                        .parse()
+                        .map_err(|_| at_line(AocError::invalid(line, "Year must be a number")))?
+                }
+                _ => return Err(at_line(AocError::invalid(line, "Unknown setting"))),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Reads the config file, if there is one, then applies any environment variables on top.
+    pub fn load(path: &str) -> Result<Config> {
+        let mut config = if Path::new(path).exists() {
+            let lines = common::read_lines(path)?;
+            Config::parse(&lines).map_err(|e| e.in_file(path))?
+        } else {
+            Config::default()
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+
+        Ok(config)
+    }
+
+    pub fn session(&self) -> Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            AocError::Config(
+                "No session token: set AOC_SESSION, or session in aoc.toml, to your adventofcode.com session cookie"
+                    .to_string(),
+            )
+        })
+    }
+
+    /// The address of a page for this year's puzzles, such as `day/1/input`.
+    pub fn url(&self, page: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.base_url.trim_end_matches('/'),
+            self.year,
+            page
+        )
+    }
+}
+
+/// Downloads the day's input to `path`, unless it is already there. Inputs never change, so an existing
+/// file is always kept, and a download is only saved once it has fully arrived.
+pub fn fetch_input(config: &Config, day: u8, path: &str) -> Result<Fetched> {
+    if Path::new(path).exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let url = config.url(&format!("day/{}/input", day));
+    let cookie = format!("session={}", config.session()?);
+    let response = http::get(&url, &[("Cookie", &cookie)])?;
+    if !response.is_success() {
+        return Err(AocError::Http {
+            url,
+            reason: format!(
+                "{} {}",
+                response.status,
+                response.body.lines().next().unwrap_or("")
+            ),
+        });
+    }
+
+    let to_error = |source| AocError::Io {
+        file: path.to_string(),
+        source,
+    };
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir).map_err(to_error)?;
+    }
+    let partial = format!("{}.part", path);
+    fs::write(&partial, &response.body)
+        .and_then(|_| fs::rename(&partial, path))
+        .map_err(to_error)?;
+
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn should_parse_config() {
+        let config = Config::parse(&lines(
+            "# Never commit this file\nsession = \"abc123\"\nbase_url = \"http://127.0.0.1:8000/\"\nyear = 2019\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            Config {
+                base_url: "http://127.0.0.1:8000/".to_string(),
+                year: 2019,
+                session: Some("abc123".to_string()),
+            },
+            config
+        );
+        assert_eq!(
+            "http://127.0.0.1:8000/2019/day/5/input",
+            config.url("day/5/input")
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_config() {
+        assert!(Config::parse(&lines("session")).is_err());
+        assert!(Config::parse(&lines("session = abc")).is_err());
+        assert!(Config::parse(&lines("year = next")).is_err());
+        assert!(Config::parse(&lines("colour = \"red\"")).is_err());
+    }
+
+    #[test]
+    fn should_require_session() {
+        assert!(Config::default().session().is_err());
+    }
+
+    #[test]
+    fn should_not_refetch_existing_input() {
+        let config = Config {
+            base_url: "http://127.0.0.1:1".to_string(),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            Fetched::Cached,
+            fetch_input(&config, 1, "./inputs/day_1/input.txt").unwrap()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Just enough HTTP to talk to the puzzle website, without pulling in any crates.
+//!
+//! Plain `http://` URLs, such as a local mock server, are spoken to directly over a TCP socket.
+//! There's no TLS in std, so `https://` requests are handed to the `curl` command instead.
+
+use crate::common::{AocError, Result};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const USER_AGENT: &str = "advent_of_code_2018 (github.com/tohaker/AdventOfCode2018)";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(PartialEq, Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends a GET request with the given extra headers.
+pub fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response> {
+    send("GET", url, headers, None)
+}
+
+/// Sends a POST request with a form body, such as one built by `form`.
+pub fn post(url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response> {
+    let mut headers = headers.to_vec();
+    headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+    send("POST", url, &headers, Some(body))
+}
+
+/// Encodes fields as `application/x-www-form-urlencoded`.
+pub fn form(fields: &[(&str, &str)]) -> String {
+    let encode = |text: &str| {
+        text.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    (b as char).to_string()
+                }
+                b' ' => "+".to_string(),
+                _ => format!("%{:02X}", b),
+            })
+            .collect::<String>()
+    };
+
+    fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", encode(name), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn send(method: &str, url: &str, headers: &[(&str, &str)], body: Option<&str>) -> Result<Response> {
+    let to_error = |reason: String| AocError::Http {
+        url: url.to_string(),
+        reason,
+    };
+
+    if url.starts_with("https://") {
+        send_with_curl(method, url, headers, body).map_err(to_error)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        send_over_tcp(method, rest, headers, body).map_err(to_error)
+    } else {
+        Err(to_error(
+            "Only http:// and https:// URLs are supported".to_string(),
+        ))
+    }
+}
+
+/// Splits the part of a URL after `http://` into the address to connect to, the host and the path.
+fn split_url(rest: &str) -> (String, &str, &str) {
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => host,
+        _ => authority,
+    };
+    let address = if host == authority {
+        format!("{}:80", authority)
+    } else {
+        authority.to_string()
+    };
+
+    (address, authority, path)
+}
+
+fn send_over_tcp(
+    method: &str,
+    rest: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> std::result::Result<Response, String> {
+    let (address, host, path) = split_url(rest);
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+        method, path, host, USER_AGENT
+    );
+    for (name, value) in headers {
+        request += &format!("{}: {}\r\n", name, value);
+    }
+    if let Some(body) = body {
+        request += &format!("Content-Length: {}\r\n", body.len());
+    }
+    request += "\r\n";
+    request += body.unwrap_or("");
+
+    let mut stream = TcpStream::connect(&address).map_err(|e| e.to_string())?;
+    stream
+        .set_read_timeout(Some(TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+        .and_then(|_| stream.write_all(request.as_bytes()))
+        .map_err(|e| e.to_string())?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(|e| e.to_string())?;
+    parse_response(&String::from_utf8_lossy(&raw))
+}
+
+fn parse_response(raw: &str) -> std::result::Result<Response, String> {
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .ok_or("Response has no end to its headers")?;
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or("Response has no status code")?;
+    let chunked = lines.any(|l| {
+        let l = l.to_ascii_lowercase();
+        l.starts_with("transfer-encoding:") && l.contains("chunked")
+    });
+
+    let body = if chunked {
+        decode_chunks(body)?
+    } else {
+        body.to_string()
+    };
+
+    Ok(Response { status, body })
+}
+
+fn decode_chunks(mut body: &str) -> std::result::Result<String, String> {
+    let mut decoded = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or("Chunk has no size")?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap().trim(), 16)
+            .map_err(|_| format!("Invalid chunk size: {:?}", size))?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        let chunk = rest.get(..size).ok_or("Chunk is shorter than its size")?;
+        decoded += chunk;
+        body = rest[size..].trim_start_matches("\r\n");
+    }
+}
+
+/// Quotes a value for a curl config file.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Sends the request through curl. Everything, including the session cookie, goes through stdin as a
+/// config file, so none of it appears in the process list.
+fn send_with_curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> std::result::Result<Response, String> {
+    let mut config = format!(
+        "url = {}\nrequest = {}\nuser-agent = {}\nmax-time = {}\nsilent\nshow-error\nwrite-out = \"\\n%{{http_code}}\"\n",
+        quote(url),
+        quote(method),
+        quote(USER_AGENT),
+        TIMEOUT.as_secs()
+    );
+    for (name, value) in headers {
+        config += &format!("header = {}\n", quote(&format!("{}: {}", name, value)));
+    }
+    if let Some(body) = body {
+        config += &format!("data-binary = {}\n", quote(body));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("https:// needs curl, which could not be run: {}", e))?;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(config.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').ok_or("curl printed no status")?;
+    let status = status
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| format!("curl printed an invalid status: {:?}", status))?;
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_url() {
+        assert_eq!(
+            (
+                "127.0.0.1:8000".to_string(),
+                "127.0.0.1:8000",
+                "/2018/day/1/input"
+            ),
+            split_url("127.0.0.1:8000/2018/day/1/input")
+        );
+        assert_eq!(
+            ("example.com:80".to_string(), "example.com", "/"),
+            split_url("example.com")
+        );
+    }
+
+    #[test]
+    fn should_parse_response() {
+        let raw = "HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\nNot found";
+
+        assert_eq!(
+            Ok(Response {
+                status: 404,
+                body: "Not found".to_string(),
+            }),
+            parse_response(raw)
+        );
+    }
+
+    #[test]
+    fn should_decode_chunked_response() {
+        let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n+1\n\r\n4\r\n-2\n\n\r\n0\r\n\r\n";
+
+        assert_eq!("+1\n-2\n\n", parse_response(raw).unwrap().body);
+    }
+
+    #[test]
+    fn should_encode_form() {
+        assert_eq!(
+            "level=2&answer=a+b%26c%3D%C3%A9",
+            form(&[("level", "2"), ("answer", "a b&c=é")])
+        );
+    }
+
+    #[test]
+    fn should_quote_curl_values() {
+        assert_eq!(r#""a \"b\" \\c""#, quote(r#"a "b" \c"#));
+    }
+
+    #[test]
+    fn should_reject_unknown_schemes() {
+        assert!(get("ftp://example.com", &[]).is_err());
+    }
+}
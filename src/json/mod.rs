@@ -0,0 +1,123 @@
+use std::fmt;
+
+/// Just enough JSON to write out results, without pulling in a serialisation crate.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Json {
+    Null,
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Integer(value) => write!(f, "{}", value),
+            Json::Float(value) if value.is_finite() => write!(f, "{}", value),
+            Json::Float(_) => write!(f, "null"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Json {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Json {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Json {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_write_scalars() {
+        assert_eq!("null", Json::Null.to_string());
+        assert_eq!("-12", Json::Integer(-12).to_string());
+        assert_eq!("1.5", Json::Float(1.5).to_string());
+        assert_eq!("null", Json::Float(f64::NAN).to_string());
+    }
+
+    #[test]
+    fn should_escape_strings() {
+        let value = Json::from("say \"hi\"\n\\\u{1}");
+
+        assert_eq!("\"say \\\"hi\\\"\\n\\\\\\u0001\"", value.to_string());
+    }
+
+    #[test]
+    fn should_write_nested_values() {
+        let value = Json::Array(vec![
+            Json::object(vec![
+                ("day", Json::Integer(1)),
+                ("error", Json::from(None::<String>)),
+            ]),
+            Json::object(vec![]),
+        ]);
+
+        assert_eq!("[{\"day\":1,\"error\":null},{}]", value.to_string());
+    }
+}
@@ -0,0 +1,23 @@
+//! Solutions to the Advent of Code 2018, along with the pieces needed to run and check them.
+//!
+//! Each `day_N` module exposes the functions that solve that day's puzzle, and a type implementing
+//! [`solution::Solution`] which is registered in [`solution::registry`].
+
+pub mod answers;
+pub mod cli;
+pub mod common;
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod examples;
+pub mod fetch;
+pub mod http;
+pub mod json;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod watch;
@@ -1,22 +1,28 @@
-mod common;
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
+use advent_of_code_2018::cli::{self, commands, Command};
+use std::env;
+use std::process::ExitCode;
 
-fn main() {
-    day_1::part_1();
-    day_1::part_2();
-    day_2::part_1();
-    day_2::part_2();
-    day_3::part_1();
-    day_3::part_2();
-    day_4::part_1();
-    day_4::part_2();
-    day_5::part_1();
-    day_5::part_2();
-    day_6::part_1();
-    day_6::part_2();
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match cli::parse(&args) {
+        Ok(Command::Run(options)) => commands::run(options),
+        Ok(Command::Bench(options)) => commands::bench(options),
+        Ok(Command::Verify(options)) => commands::verify(options),
+        Ok(Command::Solve(options)) => commands::solve(options),
+        Ok(Command::Fetch(options)) => commands::fetch(options),
+        Ok(Command::Submit(options)) => commands::submit(options),
+        Ok(Command::Watch(options)) => commands::watch(options),
+        Ok(Command::Report(options)) => commands::report(options),
+        Ok(Command::NewDay(options)) => commands::new_day(options),
+        Ok(Command::List) => commands::list(),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            ExitCode::from(2)
+        }
+    }
 }
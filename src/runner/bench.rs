@@ -0,0 +1,164 @@
+use super::timed;
+use crate::common::{self, AocError, Result};
+use crate::solution::Day;
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Phase {
+    Read,
+    Parse,
+    Part(u8),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Read => write!(f, "Read"),
+            Phase::Parse => write!(f, "Parse"),
+            Phase::Part(p) => write!(f, "Part {}", p),
+        }
+    }
+}
+
+/// Summary of repeated timings of a single phase.
+#[derive(PartialEq, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// `None` if there are no timings to summarise.
+    pub fn new(mut samples: Vec<Duration>) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Some(Stats {
+            runs: samples.len(),
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+fn no_iterations() -> AocError {
+    AocError::Config("Benchmarking needs at least one iteration".to_string())
+}
+
+/// Reads and parses the day's input, then solves each of the requested parts, repeating every phase
+/// `iterations` times.
+/// Stops at the first error, since timings of a failing solver aren't worth much.
+pub fn bench_day(
+    day: &Day,
+    parts: &[u8],
+    input_path: &str,
+    iterations: usize,
+) -> Result<Vec<(Phase, Stats)>> {
+    let mut result = Vec::new();
+
+    let mut samples = Vec::new();
+    let mut lines = None;
+    for _ in 0..iterations {
+        let (read, duration) = timed(|| common::read_input(input_path));
+        lines = Some(read?);
+        samples.push(duration);
+    }
+    result.push((Phase::Read, Stats::new(samples).ok_or_else(no_iterations)?));
+
+    let lines = lines.ok_or_else(no_iterations)?;
+    let mut samples = Vec::new();
+    let mut input = None;
+    for _ in 0..iterations {
+        let lines = lines.clone();
+        let (parsed, duration) = timed(|| day.parse(lines));
+        input = Some(parsed.map_err(|e| e.in_file(input_path))?);
+        samples.push(duration);
+    }
+    result.push((Phase::Parse, Stats::new(samples).ok_or_else(no_iterations)?));
+
+    let input = input.ok_or_else(no_iterations)?;
+    for p in parts.iter() {
+        let mut samples = Vec::new();
+        for _ in 0..iterations {
+            let (solved, duration) = timed(|| day.solve(*p, &input));
+            solved?;
+            samples.push(duration);
+        }
+        result.push((
+            Phase::Part(*p),
+            Stats::new(samples).ok_or_else(no_iterations)?,
+        ));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn should_summarise_odd_samples() {
+        let stats = Stats::new(vec![ms(5), ms(1), ms(3)]).unwrap();
+
+        assert_eq!(
+            Stats {
+                runs: 3,
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn should_summarise_even_samples() {
+        let stats = Stats::new(vec![ms(4), ms(1), ms(2), ms(8)]).unwrap();
+
+        assert_eq!(ms(3), stats.median);
+        assert_eq!(None, Stats::new(Vec::new()));
+    }
+
+    #[test]
+    fn should_bench_each_phase() {
+        let day = solution::find(1).unwrap();
+        let result = bench_day(&day, &[1, 2], "./inputs/day_1/input.txt", 2).unwrap();
+        let phases: Vec<Phase> = result.iter().map(|(phase, _)| *phase).collect();
+
+        assert_eq!(
+            vec![Phase::Read, Phase::Parse, Phase::Part(1), Phase::Part(2)],
+            phases
+        );
+        assert!(result.iter().all(|(_, stats)| stats.runs == 2));
+    }
+
+    #[test]
+    fn should_refuse_to_bench_without_iterations() {
+        let day = solution::find(1).unwrap();
+        let error = bench_day(&day, &[1], "./inputs/day_1/input.txt", 0).unwrap_err();
+
+        assert_eq!(
+            "Benchmarking needs at least one iteration",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use super::{timed, DayRun, Limits, PartRun};
+use crate::common::{AocError, Result};
+use crate::solution::{Answer, Day, PartResult};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Turns the printed form of an answer back into an `Answer`.
+pub fn parse_answer(output: &str) -> Answer {
+    let output = output.strip_suffix('\n').unwrap_or(output);
+
+    if let Some(lines) = output.strip_prefix('\n') {
+        Answer::MultiLine(lines.lines().map(String::from).collect())
+    } else if let Ok(value) = output.parse::<i64>() {
+        Answer::Integer(value)
+    } else {
+        Answer::Text(output.to_string())
+    }
+}
+
+fn command(exe: &Path, day: &Day, part: u8, input_path: &str, limits: &Limits) -> Result<Command> {
+    let mut args = vec![
+        "solve".to_string(),
+        "--day".to_string(),
+        day.number.to_string(),
+        "--part".to_string(),
+        part.to_string(),
+        "--input".to_string(),
+        input_path.to_string(),
+    ];
+    args.extend(day.solve_args.iter().map(|arg| arg.to_string()));
+
+    let mut command = match limits.memory_mb {
+        None => Command::new(exe),
+        Some(_) if !cfg!(unix) => {
+            return Err(AocError::Child(
+                "Memory limits are only supported on Unix".to_string(),
+            ))
+        }
+        Some(mb) => {
+            // Let the shell apply the limit, then replace itself with the solver.
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg("ulimit -v \"$0\" && exec \"$@\"")
+                .arg((mb * 1024).to_string())
+                .arg(exe);
+            command
+        }
+    };
+
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    Ok(command)
+}
+
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// Waits for the child to exit, killing it if it outlives the timeout.
+fn wait(child: &mut Child, timeout: Option<Duration>) -> Result<ExitStatus> {
+    let start = Instant::now();
+    let to_error = |source| AocError::Child(format!("Could not wait for solver: {}", source));
+
+    loop {
+        if let Some(status) = child.try_wait().map_err(to_error)? {
+            return Ok(status);
+        }
+
+        if let Some(timeout) = timeout {
+            if start.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AocError::TimedOut(timeout));
+            }
+        }
+
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Picks out the message of a panic from the child's stderr, dropping the backtrace hint.
+fn panic_message(stderr: &str) -> String {
+    stderr
+        .lines()
+        .skip_while(|l| !l.contains("panicked at"))
+        .skip(1)
+        .take_while(|l| !l.starts_with("note:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn solve_part(
+    exe: &Path,
+    day: &Day,
+    part: u8,
+    input_path: &str,
+    limits: &Limits,
+) -> Result<PartResult> {
+    let mut child = command(exe, day, part, input_path, limits)?
+        .spawn()
+        .map_err(|e| AocError::Child(format!("Could not start {}: {}", exe.display(), e)))?;
+
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let status = wait(&mut child, limits.timeout)?;
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if status.success() {
+        return Ok(PartResult {
+            day: day.number,
+            part,
+            answer: parse_answer(&stdout),
+        });
+    }
+
+    Err(match (limits.memory_mb, status.code()) {
+        (Some(mb), _) if stderr.contains("memory allocation of") => AocError::MemoryExceeded(mb),
+        (_, Some(101)) => AocError::Panicked(panic_message(&stderr)),
+        _ if !stderr.trim().is_empty() => AocError::Child(stderr.trim().to_string()),
+        _ => AocError::Child(format!("Solver exited with {}", status)),
+    })
+}
+
+/// Solves each part in a fresh child process, so it can be killed on timeout and kept under a memory limit.
+pub fn solve_day(exe: &Path, day: &Day, parts: &[u8], input_path: &str, limits: &Limits) -> DayRun {
+    let parts = parts
+        .iter()
+        .map(|p| {
+            let (result, duration) = timed(|| solve_part(exe, day, *p, input_path, limits));
+            PartRun {
+                part: *p,
+                result,
+                duration,
+            }
+        })
+        .collect();
+
+    DayRun {
+        read: Duration::default(),
+        parse: Duration::default(),
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_printed_answers() {
+        assert_eq!(Answer::Integer(-6), parse_answer("-6\n"));
+        assert_eq!(Answer::Text("fgij".to_string()), parse_answer("fgij\n"));
+        assert_eq!(
+            Answer::MultiLine(vec!["#..".to_string(), ".#.".to_string()]),
+            parse_answer(&format!(
+                "{}\n",
+                Answer::MultiLine(vec!["#..".to_string(), ".#.".to_string()])
+            ))
+        );
+    }
+
+    #[test]
+    fn should_find_panic_message() {
+        let stderr = "\nthread 'main' panicked at src/day_6/mod.rs:10:5:\nno points\nnote: run with `RUST_BACKTRACE=1`\n";
+
+        assert_eq!("no points", panic_message(stderr));
+    }
+
+    #[test]
+    fn should_pass_day_variant_to_child() {
+        let day = crate::day_1::day(crate::day_1::frequency::FrequencyType::Big);
+        let command = command(Path::new("aoc"), &day, 2, "in.txt", &Limits::default()).unwrap();
+        let args: Vec<_> = command.get_args().map(|a| a.to_str().unwrap()).collect();
+
+        assert_eq!(
+            vec![
+                "solve",
+                "--day",
+                "1",
+                "--part",
+                "2",
+                "--input",
+                "in.txt",
+                "--frequency",
+                "big"
+            ],
+            args
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::common::{self, AocError, Result};
+use crate::solution::{Day, PartResult};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+mod bench;
+mod isolate;
+mod pool;
+
+pub use bench::bench_day;
+pub use pool::{solve_all, Job, Summary};
+
+/// The outcome of one part, and how long it took to solve.
+pub struct PartRun {
+    pub part: u8,
+    pub result: Result<PartResult>,
+    pub duration: Duration,
+}
+
+/// The outcome of every requested part of a day, along with how long reading and parsing took.
+/// When each part runs in its own process, reading and parsing are counted in the part's time instead.
+pub struct DayRun {
+    pub read: Duration,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Runs the closure, returning its result and how long it took.
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs a solver, turning a panic into an `AocError::Panicked` so one bad day can't take down the rest.
+pub fn catch_panic<T, F: FnOnce() -> Result<T>>(f: F) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(AocError::Panicked(panic_message(payload))))
+}
+
+/// Limits on how long, and with how much memory, each solver may run.
+#[derive(Clone, Default, Debug)]
+pub struct Limits {
+    /// Wall-clock time allowed for parsing, and then for each part.
+    pub timeout: Option<Duration>,
+    /// Runs each part in a child process of this executable, using its `solve` command.
+    /// Unlike a thread, a child that runs out of time can be killed rather than abandoned.
+    pub isolate: Option<PathBuf>,
+    /// Memory ceiling in megabytes for each child process. Only enforced along with `isolate`.
+    pub memory_mb: Option<u64>,
+}
+
+/// Runs a solver on its own thread if there is a timeout, giving up on it once the time has passed.
+/// A solver that never finishes can't be stopped, so its thread is left running in the background.
+fn with_timeout<T, F>(timeout: Option<Duration>, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return catch_panic(f),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(catch_panic(f)));
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(AocError::TimedOut(timeout)),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(AocError::Panicked("Solver thread stopped".to_string()))
+        }
+    }
+}
+
+/// Reads and parses the day's input, then solves each of the requested parts in the order they were asked for.
+pub fn solve_day(day: &Day, parts: &[u8], input_path: &str, limits: &Limits) -> Result<DayRun> {
+    if let Some(exe) = &limits.isolate {
+        return Ok(isolate::solve_day(exe, day, parts, input_path, limits));
+    }
+
+    let day = *day;
+    let (lines, read) = timed(|| common::read_input(input_path));
+    let lines = lines?;
+    let (input, parse) = timed(|| with_timeout(limits.timeout, move || day.parse(lines)));
+    let input = Arc::new(input.map_err(|e| e.in_file(input_path))?);
+
+    let parts = parts
+        .iter()
+        .map(|p| {
+            let (part, input) = (*p, Arc::clone(&input));
+            let (result, duration) =
+                timed(|| with_timeout(limits.timeout, move || day.solve(part, &input)));
+            PartRun {
+                part,
+                result,
+                duration,
+            }
+        })
+        .collect();
+
+    Ok(DayRun { read, parse, parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{self, Answer, Solution};
+
+    struct Panics;
+
+    struct Hangs;
+
+    impl Solution for Hangs {
+        type Input = ();
+
+        fn parse(_: Vec<String>) -> Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_1(_: &Self::Input) -> Result<Answer> {
+            Ok(Answer::Integer(1))
+        }
+
+        fn part_2(_: &Self::Input) -> Result<Answer> {
+            loop {
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+    }
+
+    impl Solution for Panics {
+        type Input = ();
+
+        fn parse(_: Vec<String>) -> Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_1(_: &Self::Input) -> Result<Answer> {
+            Ok(Answer::Integer(1))
+        }
+
+        fn part_2(_: &Self::Input) -> Result<Answer> {
+            panic!("Part 2 is broken")
+        }
+    }
+
+    #[test]
+    fn should_solve_requested_parts() {
+        let day = solution::find(1).unwrap();
+        let run = solve_day(&day, &[2], "./inputs/day_1/input.txt", &Limits::default()).unwrap();
+
+        assert_eq!(1, run.parts.len());
+        assert_eq!(2, run.parts[0].part);
+        assert_eq!(2, run.parts[0].result.as_ref().unwrap().part);
+    }
+
+    #[test]
+    fn should_fail_on_missing_input() {
+        let day = solution::find(1).unwrap();
+
+        assert!(solve_day(
+            &day,
+            &[1, 2],
+            "./inputs/test/non-existent.txt",
+            &Limits::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_name_file_in_parse_errors() {
+        let day = solution::find(1).unwrap();
+        let error = solve_day(&day, &[1], "./inputs/test/test.txt", &Limits::default())
+            .err()
+            .unwrap();
+
+        assert!(error.to_string().starts_with("./inputs/test/test.txt:1:"));
+    }
+
+    #[test]
+    fn should_catch_panicking_parts() {
+        let day = Day::new::<Panics>(99, "Panics");
+        let run = solve_day(&day, &[1, 2], "./inputs/test/test.txt", &Limits::default()).unwrap();
+
+        assert!(run.parts[0].result.is_ok());
+        match &run.parts[1].result {
+            Err(AocError::Panicked(message)) => assert_eq!("Part 2 is broken", message),
+            _ => panic!("Expected part 2 to panic"),
+        }
+    }
+
+    #[test]
+    fn should_time_out_hanging_parts() {
+        let day = Day::new::<Hangs>(99, "Hangs");
+        let limits = Limits {
+            timeout: Some(Duration::from_millis(50)),
+            ..Limits::default()
+        };
+        let run = solve_day(&day, &[1, 2], "./inputs/test/test.txt", &limits).unwrap();
+
+        assert!(run.parts[0].result.is_ok());
+        match &run.parts[1].result {
+            Err(AocError::TimedOut(limit)) => assert_eq!(Duration::from_millis(50), *limit),
+            _ => panic!("Expected part 2 to time out"),
+        }
+    }
+}
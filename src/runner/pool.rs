@@ -0,0 +1,157 @@
+use super::{solve_day, DayRun, Limits};
+use crate::common::{AocError, Result};
+use crate::solution::Day;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// A day to solve, along with which parts and which input to use.
+pub struct Job {
+    pub day: Day,
+    pub parts: Vec<u8>,
+    pub input_path: String,
+}
+
+/// Solves every job on a pool of `workers` threads.
+/// Results come back in the same order as the jobs, however long each one took.
+pub fn solve_all(jobs: &[Job], workers: usize, limits: &Limits) -> Vec<Result<DayRun>> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, jobs.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let job = match jobs.get(index) {
+                    Some(job) => job,
+                    None => break,
+                };
+
+                let run = solve_day(&job.day, &job.parts, &job.input_path, limits);
+                if sender.send((index, run)).is_err() {
+                    break;
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<Option<Result<DayRun>>> = jobs.iter().map(|_| None).collect();
+    for (index, run) in receiver {
+        results[index] = Some(run);
+    }
+
+    results
+        .into_iter()
+        .map(|r| r.expect("Every job sends back a result"))
+        .collect()
+}
+
+/// How many parts were solved, failed with an error, panicked or ran out of time.
+#[derive(PartialEq, Debug, Default)]
+pub struct Summary {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub panicked: usize,
+    pub timed_out: usize,
+}
+
+impl Summary {
+    pub fn record<T>(&mut self, result: &Result<T>) {
+        match result {
+            Ok(_) => self.succeeded += 1,
+            Err(AocError::Panicked(_)) => self.panicked += 1,
+            Err(AocError::TimedOut(_)) => self.timed_out += 1,
+            Err(_) => self.failed += 1,
+        }
+    }
+
+    /// Records the outcome of a whole job, where an input error counts against every part.
+    pub fn record_run(&mut self, job: &Job, run: &Result<DayRun>) {
+        match run {
+            Ok(run) => run.parts.iter().for_each(|p| self.record(&p.result)),
+            Err(AocError::Panicked(_)) => self.panicked += job.parts.len(),
+            Err(AocError::TimedOut(_)) => self.timed_out += job.parts.len(),
+            Err(_) => self.failed += job.parts.len(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failed == 0 && self.panicked == 0 && self.timed_out == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+    use std::time::Duration;
+
+    fn job(number: u8, input_path: &str) -> Job {
+        Job {
+            day: solution::find(number).unwrap(),
+            parts: vec![1, 2],
+            input_path: input_path.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_keep_results_in_job_order() {
+        let jobs = vec![
+            job(6, "./inputs/day_6/input.txt"),
+            job(1, "./inputs/test/non-existent.txt"),
+            job(4, "./inputs/day_4/input.txt"),
+        ];
+
+        let results = solve_all(&jobs, 3, &Limits::default());
+
+        assert_eq!(3, results.len());
+        assert_eq!(
+            6,
+            results[0].as_ref().unwrap().parts[0]
+                .result
+                .as_ref()
+                .unwrap()
+                .day
+        );
+        assert!(results[1].is_err());
+        assert_eq!(
+            4,
+            results[2].as_ref().unwrap().parts[0]
+                .result
+                .as_ref()
+                .unwrap()
+                .day
+        );
+    }
+
+    #[test]
+    fn should_summarise_results() {
+        let jobs = vec![
+            job(1, "./inputs/test/non-existent.txt"),
+            job(4, "./inputs/day_4/input.txt"),
+        ];
+        let results = solve_all(&jobs, 2, &Limits::default());
+
+        let mut summary = Summary::default();
+        for (job, run) in jobs.iter().zip(results.iter()) {
+            summary.record_run(job, run);
+        }
+        summary.record::<()>(&Err(AocError::Panicked("boom".to_string())));
+        summary.record::<()>(&Err(AocError::TimedOut(Duration::from_secs(1))));
+
+        assert_eq!(
+            Summary {
+                succeeded: 2,
+                failed: 2,
+                panicked: 1,
+                timed_out: 1
+            },
+            summary
+        );
+        assert!(!summary.is_success());
+    }
+}
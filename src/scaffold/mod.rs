@@ -0,0 +1,194 @@
+//! Generates the boilerplate for a new day: its module, its input directory, and its place in the registry.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = r#"use crate::common::{self, AocError, Result};
+use crate::solution::{Answer, Solution};
+
+/// Reads a single line of the puzzle input.
+pub fn parse_line(line: &str) -> Result<String> {
+    Ok(line.to_string())
+}
+
+pub struct {name};
+
+impl Solution for {name} {
+    type Input = Vec<String>;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input> {
+        common::parse_lines(&lines, parse_line)
+    }
+
+    fn part_1(_input: &Self::Input) -> Result<Answer> {
+        Err(AocError::no_solution("Part 1 has not been solved yet"))
+    }
+
+    fn part_2(_input: &Self::Input) -> Result<Answer> {
+        Err(AocError::no_solution("Part 2 has not been solved yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_line() {
+        assert_eq!("example", parse_line("example").unwrap());
+    }
+}
+"#;
+
+/// The name of a day's solution type, such as `ChronalCalibration` for "Chronal Calibration".
+fn type_name(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            format!("{}{}", first, chars.as_str())
+        })
+        .collect()
+}
+
+/// The day a line refers to, if it is one of the lines found by `prefix`, such as `pub mod day_`.
+fn day_of(line: &str, prefix: &str) -> Option<u8> {
+    let rest = &line[line.find(prefix)? + prefix.len()..];
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Inserts a line among those for other days, keeping them in numerical order.
+fn insert_in_order(source: &str, prefix: &str, number: u8, line: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| day_of(l, prefix).map(|d| (i, d)))
+        .collect();
+
+    if days.iter().any(|&(_, d)| d == number) {
+        return Err(format!("Day {} is already registered", number));
+    }
+
+    let index = match days.iter().rev().find(|&&(_, d)| d < number) {
+        Some(&(i, _)) => i + 1,
+        None => match days.first() {
+            Some(&(i, _)) => i,
+            None => return Err(format!("Could not find any lines containing {}", prefix)),
+        },
+    };
+    lines.insert(index, line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Could not write {}: {}", path.display(), e))
+}
+
+/// Creates `src/day_N/mod.rs` and `inputs/day_N/` under `root`, and registers the day in `src/lib.rs`
+/// and `src/solution/mod.rs`. Returns every file and directory that was created or changed.
+pub fn new_day(root: &Path, number: u8, title: Option<&str>) -> Result<Vec<PathBuf>, String> {
+    let default_title = format!("Day {}", number);
+    let title = title.unwrap_or(&default_title);
+    let name = type_name(title);
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(format!("Cannot name a solution after {:?}", title));
+    }
+
+    let module_dir = root.join(format!("src/day_{}", number));
+    if module_dir.exists() {
+        return Err(format!("{} already exists", module_dir.display()));
+    }
+
+    let lib = root.join("src/lib.rs");
+    let registry = root.join("src/solution/mod.rs");
+    let module_line = format!("pub mod day_{};", number);
+    let registry_line = format!(
+        "        Day::new::<crate::day_{}::{}>({}, {:?}),",
+        number, name, number, title
+    );
+
+    // Work out both registrations before touching anything, so a failure leaves no half-made day.
+    let lib_source = insert_in_order(&read(&lib)?, "pub mod day_", number, &module_line)?;
+    let registry_source =
+        insert_in_order(&read(&registry)?, "crate::day_", number, &registry_line)?;
+
+    let module = module_dir.join("mod.rs");
+    let inputs = root.join(format!("inputs/day_{}/examples", number));
+    fs::create_dir_all(&module_dir)
+        .and_then(|_| fs::create_dir_all(&inputs))
+        .map_err(|e| format!("Could not create day {}: {}", number, e))?;
+    write(&module, &TEMPLATE.replace("{name}", &name))?;
+    write(&lib, &lib_source)?;
+    write(&registry, &registry_source)?;
+
+    Ok(vec![module, inputs, lib, registry])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn should_name_type_after_title() {
+        assert_eq!("ChronalCalibration", type_name("Chronal Calibration"));
+        assert_eq!(
+            "NoMatterHowYouSliceIt",
+            type_name("No Matter How You Slice It?")
+        );
+        assert_eq!("Day7", type_name("Day 7"));
+    }
+
+    #[test]
+    fn should_insert_days_in_order() {
+        let source =
+            "pub mod answers;\npub mod day_1;\npub mod day_2;\npub mod day_10;\npub mod json;\n";
+
+        assert_eq!(
+            "pub mod answers;\npub mod day_1;\npub mod day_2;\npub mod day_3;\npub mod day_10;\npub mod json;\n",
+            insert_in_order(source, "pub mod day_", 3, "pub mod day_3;").unwrap()
+        );
+        assert_eq!(
+            "pub mod answers;\npub mod day_1;\npub mod day_2;\npub mod day_10;\npub mod day_11;\npub mod json;\n",
+            insert_in_order(source, "pub mod day_", 11, "pub mod day_11;").unwrap()
+        );
+        assert!(insert_in_order(source, "pub mod day_", 2, "pub mod day_2;").is_err());
+        assert!(insert_in_order("", "pub mod day_", 2, "pub mod day_2;").is_err());
+    }
+
+    #[test]
+    fn should_scaffold_new_day() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/solution")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod common;\npub mod day_1;\n").unwrap();
+        fs::write(
+            root.join("src/solution/mod.rs"),
+            "    vec![\n        Day::new::<crate::day_1::ChronalCalibration>(1, \"Chronal Calibration\"),\n    ]\n",
+        )
+        .unwrap();
+
+        new_day(&root, 2, Some("Inventory Management System")).unwrap();
+
+        let module = fs::read_to_string(root.join("src/day_2/mod.rs")).unwrap();
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        let registry = fs::read_to_string(root.join("src/solution/mod.rs")).unwrap();
+        let existing = new_day(&root, 2, None);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(module.contains("pub struct InventoryManagementSystem;"));
+        assert!(lib.ends_with("pub mod day_1;\npub mod day_2;\n"));
+        assert!(registry.contains(
+            "        Day::new::<crate::day_2::InventoryManagementSystem>(2, \"Inventory Management System\"),\n"
+        ));
+        assert!(existing.is_err());
+    }
+}
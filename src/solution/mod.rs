@@ -0,0 +1,259 @@
+use crate::common::{BigInt, Result};
+use crate::json::Json;
+use std::any::Any;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A puzzle answer, typed so it can be compared and serialised rather than just printed.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    MultiLine(Vec<String>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::MultiLine(lines) => write!(f, "\n{}", lines.join("\n")),
+        }
+    }
+}
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Json {
+        match answer {
+            Answer::Integer(value) => Json::Integer(*value),
+            Answer::Text(value) => Json::from(value.as_str()),
+            Answer::MultiLine(lines) => Json::from(lines.join("\n")),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        Answer::Integer(value)
+    }
+}
+
+/// Too large for an integer answer, the value is given as text.
+impl From<i128> for Answer {
+    fn from(value: i128) -> Answer {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+/// Too large for an integer answer, the value is given as text.
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Answer {
+        match value.to_i64() {
+            Some(value) => Answer::Integer(value),
+            None => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Answer {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        Answer::Integer(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+/// The answer to one part of one day.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+}
+
+/// A single day's puzzle. The input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input: Send + Sync + 'static;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input>;
+    fn part_1(input: &Self::Input) -> Result<Answer>;
+    fn part_2(input: &Self::Input) -> Result<Answer>;
+}
+
+/// Parsed input for a registered day, with its concrete type erased.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// A registered day. Holds plain function pointers so it can be copied around freely.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    /// Arguments that make `aoc solve` pick this same variant of the day, for isolated parts.
+    pub solve_args: &'static [&'static str],
+    parse: fn(Vec<String>) -> Result<Parsed>,
+    part_1: fn(&Parsed) -> Result<Answer>,
+    part_2: fn(&Parsed) -> Result<Answer>,
+}
+
+fn parse<S: Solution>(lines: Vec<String>) -> Result<Parsed> {
+    Ok(Box::new(S::parse(lines)?))
+}
+
+fn downcast<S: Solution>(input: &Parsed) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .expect("Input was parsed by a different day")
+}
+
+/// Where the puzzle input for a day is kept, whether or not the day has been solved.
+pub fn input_path(number: u8) -> String {
+    format!("./inputs/day_{}/input.txt", number)
+}
+
+impl Day {
+    pub fn new<S: Solution>(number: u8, title: &'static str) -> Day {
+        Day {
+            number,
+            title,
+            solve_args: &[],
+            parse: parse::<S>,
+            part_1: |input| S::part_1(downcast::<S>(input)),
+            part_2: |input| S::part_2(downcast::<S>(input)),
+        }
+    }
+
+    /// The same day, solved in a child process with these extra arguments to `aoc solve`.
+    pub fn with_solve_args(self, solve_args: &'static [&'static str]) -> Day {
+        Day { solve_args, ..self }
+    }
+
+    pub fn input_path(&self) -> String {
+        input_path(self.number)
+    }
+
+    /// Where the worked examples from the puzzle text are kept. See `examples`.
+    pub fn examples_path(&self) -> String {
+        format!("./inputs/day_{}/examples", self.number)
+    }
+
+    pub fn parse(&self, lines: Vec<String>) -> Result<Parsed> {
+        (self.parse)(lines)
+    }
+
+    /// Solves part 1 or 2 of this day.
+    pub fn solve(&self, part: u8, input: &Parsed) -> Result<PartResult> {
+        let answer = match part {
+            1 => (self.part_1)(input)?,
+            2 => (self.part_2)(input)?,
+            _ => panic!("Day {} has no part {}", self.number, part),
+        };
+
+        Ok(PartResult {
+            day: self.number,
+            part,
+            answer,
+        })
+    }
+}
+
+/// Every implemented day, in calendar order. New days only need adding here.
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day::new::<crate::day_1::ChronalCalibration>(1, "Chronal Calibration"),
+        Day::new::<crate::day_2::InventoryManagementSystem>(2, "Inventory Management System"),
+        Day::new::<crate::day_3::NoMatterHowYouSliceIt>(3, "No Matter How You Slice It"),
+        Day::new::<crate::day_4::ReposeRecord>(4, "Repose Record"),
+        Day::new::<crate::day_5::AlchemicalReduction>(5, "Alchemical Reduction"),
+        Day::new::<crate::day_6::ChronalCoordinates>(6, "Chronal Coordinates"),
+    ]
+}
+
+/// Finds a registered day by its number.
+pub fn find(number: u8) -> Option<Day> {
+    registry().into_iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_register_days_in_order() {
+        let numbers: Vec<u8> = registry().iter().map(|d| d.number).collect();
+
+        assert_eq!(Some(&1), numbers.first());
+        assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn should_find_registered_day() {
+        assert_eq!("Repose Record", find(4).unwrap().title);
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn should_run_parts_through_registry() {
+        let day = registry()[0];
+        let input = day
+            .parse(vec![String::from("+1"), String::from("-1")])
+            .unwrap();
+
+        let expected = PartResult {
+            day: 1,
+            part: 2,
+            answer: Answer::Integer(0),
+        };
+
+        assert_eq!(Answer::Integer(0), day.solve(1, &input).unwrap().answer);
+        assert_eq!(expected, day.solve(2, &input).unwrap());
+    }
+
+    #[test]
+    fn should_report_parse_errors() {
+        let day = find(1).unwrap();
+
+        assert!(day.parse(vec![String::from("+x")]).is_err());
+    }
+
+    #[test]
+    fn should_display_answers() {
+        assert_eq!("-6", Answer::from(-6).to_string());
+        assert_eq!("fgij", Answer::from("fgij".to_string()).to_string());
+        assert_eq!(
+            "\n#..\n.#.",
+            Answer::MultiLine(vec!["#..".to_string(), ".#.".to_string()]).to_string()
+        );
+    }
+
+    #[test]
+    fn should_convert_answers_to_json() {
+        assert_eq!("595", Json::from(&Answer::Integer(595)).to_string());
+        assert_eq!(
+            "\"#..\\n.#.\"",
+            Json::from(&Answer::MultiLine(vec![
+                "#..".to_string(),
+                ".#.".to_string()
+            ]))
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use super::Outcome;
+use crate::common::{self, AocError, Result};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// One submitted answer, and what the website said about it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Every answer ever submitted, kept one per line as `<time> <day> <part> <outcome> <answer>`.
+#[derive(PartialEq, Debug, Default)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+fn outcome_key(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Correct => "correct".to_string(),
+        Outcome::Incorrect => "incorrect".to_string(),
+        Outcome::TooHigh => "too_high".to_string(),
+        Outcome::TooLow => "too_low".to_string(),
+        Outcome::RateLimited(Some(wait)) => format!("rate_limited:{}", wait.as_secs()),
+        Outcome::RateLimited(None) => "rate_limited".to_string(),
+        Outcome::AlreadySolved => "already_solved".to_string(),
+    }
+}
+
+fn parse_outcome_key(key: &str) -> Option<Outcome> {
+    match key {
+        "correct" => Some(Outcome::Correct),
+        "incorrect" => Some(Outcome::Incorrect),
+        "too_high" => Some(Outcome::TooHigh),
+        "too_low" => Some(Outcome::TooLow),
+        "rate_limited" => Some(Outcome::RateLimited(None)),
+        "already_solved" => Some(Outcome::AlreadySolved),
+        _ => {
+            let seconds = key.strip_prefix("rate_limited:")?.parse().ok()?;
+            Some(Outcome::RateLimited(Some(Duration::from_secs(seconds))))
+        }
+    }
+}
+
+impl Attempt {
+    fn parse(line: &str) -> Result<Attempt> {
+        let invalid = || AocError::invalid(line, "Expected <time> <day> <part> <outcome> <answer>");
+        let mut fields = line.splitn(5, ' ');
+        let mut next = || fields.next().ok_or_else(invalid);
+
+        Ok(Attempt {
+            time: next()?.parse().map_err(|_| invalid())?,
+            day: next()?.parse().map_err(|_| invalid())?,
+            part: next()?.parse().map_err(|_| invalid())?,
+            outcome: parse_outcome_key(next()?).ok_or_else(invalid)?,
+            answer: next()?.to_string(),
+        })
+    }
+}
+
+impl History {
+    pub fn parse(lines: &[String]) -> Result<History> {
+        let attempts = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+            .map(|(i, l)| Attempt::parse(l).map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<_>>()?;
+
+        Ok(History { attempts })
+    }
+
+    /// Reads the history file. One that doesn't exist yet has no attempts in it.
+    pub fn load(path: &str) -> Result<History> {
+        if !Path::new(path).exists() {
+            return Ok(History::default());
+        }
+
+        let lines = common::read_lines(path)?;
+        History::parse(&lines).map_err(|e| e.in_file(path))
+    }
+
+    /// Adds an attempt to the end of the history file, creating it if need be.
+    pub fn record(path: &str, attempt: &Attempt) -> Result<()> {
+        let line = format!(
+            "{} {} {} {} {}\n",
+            attempt.time,
+            attempt.day,
+            attempt.part,
+            outcome_key(&attempt.outcome),
+            attempt.answer
+        );
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|source| AocError::Io {
+                file: path.to_string(),
+                source,
+            })
+    }
+
+    /// Why submitting the answer at `now` would be pointless, if the history already shows it.
+    pub fn refusal(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        let rate_limited = self.attempts.iter().rev().find_map(|a| match a.outcome {
+            Outcome::RateLimited(Some(wait)) => Some(a.time + wait.as_secs()),
+            _ => None,
+        });
+        if let Some(until) = rate_limited.filter(|until| *until > now) {
+            return Some(format!(
+                "the website asked to wait another {}s",
+                until - now
+            ));
+        }
+
+        let value = answer.parse::<i64>().ok();
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+        {
+            let same = attempt.answer == answer;
+            let previous = attempt.answer.parse::<i64>().ok();
+            let refusal = match (&attempt.outcome, value.zip(previous)) {
+                (Outcome::Correct, _) if same => "it was already accepted".to_string(),
+                (Outcome::Correct, _) => format!("{} was already accepted", attempt.answer),
+                (Outcome::Incorrect, _) if same => "it was already wrong".to_string(),
+                (Outcome::TooHigh, Some((value, previous))) if value >= previous => {
+                    format!("{} was already too high", previous)
+                }
+                (Outcome::TooLow, Some((value, previous))) if value <= previous => {
+                    format!("{} was already too low", previous)
+                }
+                (Outcome::TooHigh, _) | (Outcome::TooLow, _) if same => {
+                    "it was already wrong".to_string()
+                }
+                _ => continue,
+            };
+
+            return Some(refusal);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(input: &str) -> History {
+        History::parse(&input.lines().map(String::from).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn should_parse_history() {
+        let history = history("# time day part outcome answer\n1000 2 2 correct fgij klm\n");
+
+        assert_eq!(
+            vec![Attempt {
+                time: 1000,
+                day: 2,
+                part: 2,
+                answer: "fgij klm".to_string(),
+                outcome: Outcome::Correct,
+            }],
+            history.attempts
+        );
+        assert!(History::parse(&["1000 2 2 maybe 5".to_string()]).is_err());
+        assert!(History::parse(&["1000 2 2 correct".to_string()]).is_err());
+    }
+
+    #[test]
+    fn should_refuse_known_wrong_answers() {
+        let history =
+            history("1000 1 1 incorrect 12\n1100 1 1 too_high 700\n1200 1 1 too_low 500\n");
+
+        assert_eq!(
+            Some("it was already wrong".to_string()),
+            history.refusal(1, 1, "12", 2000)
+        );
+        assert_eq!(
+            Some("700 was already too high".to_string()),
+            history.refusal(1, 1, "701", 2000)
+        );
+        assert_eq!(
+            Some("500 was already too low".to_string()),
+            history.refusal(1, 1, "500", 2000)
+        );
+        assert_eq!(None, history.refusal(1, 1, "595", 2000));
+        assert_eq!(None, history.refusal(1, 2, "12", 2000));
+    }
+
+    #[test]
+    fn should_refuse_solved_parts() {
+        let history = history("1000 1 1 correct 595\n");
+
+        assert_eq!(
+            Some("it was already accepted".to_string()),
+            history.refusal(1, 1, "595", 2000)
+        );
+        assert_eq!(
+            Some("595 was already accepted".to_string()),
+            history.refusal(1, 1, "596", 2000)
+        );
+    }
+
+    #[test]
+    fn should_refuse_while_rate_limited() {
+        let history = history("1000 1 1 rate_limited:60 595\n");
+
+        assert_eq!(
+            Some("the website asked to wait another 10s".to_string()),
+            history.refusal(2, 1, "1", 1050)
+        );
+        assert_eq!(None, history.refusal(2, 1, "1", 1060));
+    }
+
+    #[test]
+    fn should_record_attempts() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.log", std::process::id()));
+        let path = path.to_str().unwrap();
+        let attempt = Attempt {
+            time: 1000,
+            day: 6,
+            part: 1,
+            answer: "3449".to_string(),
+            outcome: Outcome::RateLimited(Some(Duration::from_secs(30))),
+        };
+
+        History::record(path, &attempt).unwrap();
+        History::record(path, &attempt).unwrap();
+        let history = History::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(vec![attempt.clone(), attempt], history.attempts);
+    }
+}
@@ -0,0 +1,156 @@
+//! Posts answers to the puzzle website, and reads back its verdict.
+//!
+//! Every attempt is kept in a local `History`, which is used to refuse answers that are already known
+//! to be wrong before the website gets the chance to say so and start a cool-down.
+
+use crate::common::{AocError, Result};
+use crate::fetch::Config;
+use crate::http;
+use std::fmt;
+use std::time::Duration;
+mod history;
+
+pub use history::{Attempt, History};
+
+/// What the website said about a submitted answer.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the last attempt, with how long was left to wait if the website said.
+    RateLimited(Option<Duration>),
+    /// The part had already been solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::RateLimited(Some(wait)) => {
+                write!(f, "not checked, {}s left to wait", wait.as_secs())
+            }
+            Outcome::RateLimited(None) => write!(f, "not checked, submitted too recently"),
+            Outcome::AlreadySolved => write!(f, "not checked, already solved"),
+        }
+    }
+}
+
+/// Reads a wait such as `You have 4m 12s left to wait`.
+fn parse_wait(body: &str) -> Option<Duration> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in body[start..end].split_whitespace() {
+        let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Reads the verdict out of the page the website returns after a submission.
+pub fn parse_outcome(body: &str) -> Option<Outcome> {
+    if body.contains("That's the right answer") {
+        Some(Outcome::Correct)
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Some(Outcome::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Some(Outcome::TooLow)
+        } else {
+            Some(Outcome::Incorrect)
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Some(Outcome::RateLimited(parse_wait(body)))
+    } else if body.contains("You don't seem to be solving the right level") {
+        Some(Outcome::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+/// Submits an answer to one part of a day.
+pub fn submit(config: &Config, day: u8, part: u8, answer: &str) -> Result<Outcome> {
+    let url = config.url(&format!("day/{}/answer", day));
+    let cookie = format!("session={}", config.session()?);
+    let body = http::form(&[("level", &part.to_string()), ("answer", answer)]);
+    let response = http::post(&url, &[("Cookie", &cookie)], &body)?;
+
+    if !response.is_success() {
+        return Err(AocError::Http {
+            url,
+            reason: format!(
+                "{} {}",
+                response.status,
+                response.body.lines().next().unwrap_or("")
+            ),
+        });
+    }
+
+    parse_outcome(&response.body).ok_or(AocError::Http {
+        url,
+        reason: "The response did not say whether the answer was right".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_outcomes() {
+        let page =
+            |message: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", message);
+
+        assert_eq!(
+            Some(Outcome::Correct),
+            parse_outcome(&page(
+                "That's the right answer!  You are one gold star closer."
+            ))
+        );
+        assert_eq!(
+            Some(Outcome::Incorrect),
+            parse_outcome(&page("That's not the right answer.  If you're stuck, ..."))
+        );
+        assert_eq!(
+            Some(Outcome::TooHigh),
+            parse_outcome(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+        );
+        assert_eq!(
+            Some(Outcome::TooLow),
+            parse_outcome(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+        );
+        assert_eq!(
+            Some(Outcome::RateLimited(Some(Duration::from_secs(252)))),
+            parse_outcome(&page(
+                "You gave an answer too recently. You have 4m 12s left to wait."
+            ))
+        );
+        assert_eq!(
+            Some(Outcome::RateLimited(None)),
+            parse_outcome(&page("You gave an answer too recently."))
+        );
+        assert_eq!(
+            Some(Outcome::AlreadySolved),
+            parse_outcome(&page("You don't seem to be solving the right level."))
+        );
+        assert_eq!(None, parse_outcome(&page("Please log in.")));
+    }
+}
@@ -0,0 +1,109 @@
+//! Notices changes to a day's inputs by polling file metadata, so it needs nothing beyond std.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// The modification time and size of every file below a directory.
+#[derive(PartialEq, Debug, Default)]
+pub struct Snapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    /// Records every file below `dir`. A directory that doesn't exist yet is simply empty.
+    pub fn take(dir: &Path) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        snapshot.add(dir);
+        snapshot
+    }
+
+    fn add(&mut self, dir: &Path) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => self.add(&path),
+                Ok(metadata) => {
+                    self.0
+                        .insert(path, (metadata.modified().ok(), metadata.len()));
+                }
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Every file that was added, changed or removed since `earlier` was taken.
+    pub fn changes(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let changed = self
+            .0
+            .iter()
+            .filter(|(path, meta)| earlier.0.get(*path) != Some(meta))
+            .map(|(path, _)| path.clone());
+        let removed = earlier
+            .0
+            .keys()
+            .filter(|path| !self.0.contains_key(*path))
+            .cloned();
+
+        changed.chain(removed).collect()
+    }
+}
+
+/// Calls `on_change` with the changed files whenever anything below `dir` changes, checking every `interval`.
+/// Never returns.
+pub fn watch<F: FnMut(&[PathBuf])>(dir: &Path, interval: Duration, mut on_change: F) -> ! {
+    let mut last = Snapshot::take(dir);
+    loop {
+        thread::sleep(interval);
+        let next = Snapshot::take(dir);
+        let changes = next.changes(&last);
+        if !changes.is_empty() {
+            on_change(&changes);
+        }
+        last = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn should_notice_added_changed_and_removed_files() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        let input = dir.join("input.txt");
+        let example = dir.join("examples/small.txt");
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::write(&input, "+1\n").unwrap();
+
+        let first = Snapshot::take(&dir);
+        let unchanged = Snapshot::take(&dir);
+        fs::write(&example, "+1\n").unwrap();
+        let added = Snapshot::take(&dir);
+        fs::write(&input, "+1\n+2\n").unwrap();
+        let changed = Snapshot::take(&dir);
+        fs::remove_file(&example).unwrap();
+        let removed = Snapshot::take(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(unchanged.changes(&first).is_empty());
+        assert_eq!(vec![example.clone()], added.changes(&unchanged));
+        assert_eq!(vec![input], changed.changes(&added));
+        assert_eq!(vec![example], removed.changes(&changed));
+    }
+
+    #[test]
+    fn should_treat_missing_directory_as_empty() {
+        assert_eq!(
+            Snapshot::default(),
+            Snapshot::take(Path::new("./inputs/test/non-existent"))
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A stand-in for the puzzle website, which gives the same response to every request.
+/// Each request is recorded without its `Host` and `User-Agent` headers, which vary between machines.
+pub fn mock_server(status: &'static str, body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&requests);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request = Vec::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_string();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("Content-Length: ") {
+                    length = value.parse().unwrap();
+                }
+                if !line.starts_with("Host:") && !line.starts_with("User-Agent:") {
+                    request.push(line);
+                }
+            }
+            if length > 0 {
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push(String::new());
+                request.push(String::from_utf8(content).unwrap());
+            }
+            seen.lock().unwrap().push(request.join("\n"));
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (base_url, requests)
+}
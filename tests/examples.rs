@@ -0,0 +1,67 @@
+//! Checks every example in `inputs/day_N/examples/` against its sidecar answers.
+//! Pass a substring, such as `cargo test --test examples day_5`, to check only matching examples.
+
+use advent_of_code_2018::answers::Verdict;
+use advent_of_code_2018::{examples, solution};
+use std::env;
+use std::process;
+
+fn main() {
+    let filter = env::args().skip(1).find(|a| !a.starts_with('-'));
+    let (mut passed, mut failed) = (0, 0);
+
+    for day in solution::registry() {
+        let found = match examples::find(&day) {
+            Ok(found) => found,
+            Err(e) => {
+                println!("test day_{} ... FAILED\n    {}", day.number, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for example in found {
+            let prefix = format!("day_{}::{}", day.number, example.name);
+            if filter
+                .as_ref()
+                .is_some_and(|f| !prefix.contains(f.as_str()))
+            {
+                continue;
+            }
+
+            let checks = match example.check() {
+                Ok(checks) => checks,
+                Err(e) => {
+                    println!("test {} ... FAILED\n    {}", prefix, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            for check in checks {
+                let name = format!("{}::part_{}", prefix, check.part);
+                match check.result {
+                    Ok(Verdict::Pass) => {
+                        println!("test {} ... ok", name);
+                        passed += 1;
+                        continue;
+                    }
+                    Ok(Verdict::Fail(expected)) => println!(
+                        "test {} ... FAILED\n    expected {}, got {}",
+                        name,
+                        expected,
+                        check.answer.map_or(String::new(), |a| a.to_string())
+                    ),
+                    Ok(Verdict::Unknown) => println!("test {} ... FAILED\n    no answer", name),
+                    Err(e) => println!("test {} ... FAILED\n    {}", name, e),
+                }
+                failed += 1;
+            }
+        }
+    }
+
+    println!("\nexamples: {} passed; {} failed", passed, failed);
+    if failed > 0 {
+        process::exit(1);
+    }
+}
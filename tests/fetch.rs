@@ -0,0 +1,57 @@
+use advent_of_code_2018::fetch::{self, Config, Fetched};
+use common::mock_server;
+use std::fs;
+mod common;
+
+fn temp_input(name: &str) -> String {
+    let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("day_3/input.txt").display().to_string()
+}
+
+#[test]
+fn should_download_input_once() {
+    let (base_url, requests) = mock_server("200 OK", "+1\n-2\n");
+    let config = Config {
+        base_url,
+        year: 2018,
+        session: Some("secret".to_string()),
+    };
+    let path = temp_input("once");
+
+    assert_eq!(
+        Fetched::Downloaded,
+        fetch::fetch_input(&config, 3, &path).unwrap()
+    );
+    assert_eq!(
+        Fetched::Cached,
+        fetch::fetch_input(&config, 3, &path).unwrap()
+    );
+    assert_eq!("+1\n-2\n", fs::read_to_string(&path).unwrap());
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        vec!["GET /2018/day/3/input HTTP/1.1\nConnection: close\nCookie: session=secret"],
+        *requests.lock().unwrap()
+    );
+}
+
+#[test]
+fn should_not_save_failed_download() {
+    let (base_url, _) = mock_server(
+        "400 Bad Request",
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+    );
+    let config = Config {
+        base_url,
+        year: 2018,
+        session: Some("expired".to_string()),
+    };
+    let path = temp_input("failed");
+
+    let error = fetch::fetch_input(&config, 3, &path).unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("400 Puzzle inputs differ by user."));
+    assert!(fs::metadata(&path).is_err());
+}
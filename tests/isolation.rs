@@ -0,0 +1,99 @@
+use advent_of_code_2018::common::AocError;
+use advent_of_code_2018::day_1::{self, frequency::FrequencyType};
+use advent_of_code_2018::runner::{self, Limits};
+use advent_of_code_2018::solution::{self, Answer};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+fn isolated(timeout: Duration) -> Limits {
+    Limits {
+        timeout: Some(timeout),
+        isolate: Some(PathBuf::from(env!("CARGO_BIN_EXE_aoc"))),
+        memory_mb: None,
+    }
+}
+
+#[test]
+fn should_solve_parts_in_child_processes() {
+    let day = solution::find(1).unwrap();
+    let limits = isolated(Duration::from_secs(30));
+    let run = runner::solve_day(&day, &[1], "./inputs/day_1/input.txt", &limits).unwrap();
+
+    assert_eq!(
+        Answer::Integer(595),
+        run.parts[0].result.as_ref().unwrap().answer
+    );
+}
+
+#[test]
+fn should_solve_chosen_frequency_type_in_child_processes() {
+    let day = day_1::day(FrequencyType::Big);
+    let limits = isolated(Duration::from_secs(30));
+    let input = env::temp_dir().join(format!("aoc-past-i64-{}.txt", process::id()));
+    fs::write(&input, format!("+{0}\n+{0}\n", i64::MAX)).unwrap();
+    let run = runner::solve_day(&day, &[1], input.to_str().unwrap(), &limits).unwrap();
+    fs::remove_file(&input).unwrap();
+
+    assert_eq!(
+        Answer::Text("18446744073709551614".to_string()),
+        run.parts[0].result.as_ref().unwrap().answer
+    );
+}
+
+/// Box IDs that all differ from each other in at least two places, so day 2 compares every pair
+/// without finding an answer, which takes far longer than any of these tests allow.
+fn unmatched_box_ids() -> PathBuf {
+    let path = env::temp_dir().join(format!("aoc-unmatched-{}.txt", process::id()));
+    let ids: Vec<String> = (0..5000u32)
+        .map(|i| {
+            let letters: String = [i / 676, i / 26 % 26, i % 26]
+                .iter()
+                .map(|d| char::from(b'a' + *d as u8))
+                .collect();
+            letters.repeat(3)
+        })
+        .collect();
+    fs::write(&path, ids.join("\n")).unwrap();
+    path
+}
+
+#[test]
+fn should_kill_child_that_never_finishes() {
+    let day = solution::find(2).unwrap();
+    let limits = isolated(Duration::from_millis(200));
+    let input = unmatched_box_ids();
+    let run = runner::solve_day(&day, &[2], input.to_str().unwrap(), &limits).unwrap();
+    fs::remove_file(&input).unwrap();
+
+    match &run.parts[0].result {
+        Err(AocError::TimedOut(_)) => (),
+        _ => panic!("Expected the child to time out"),
+    }
+}
+
+#[test]
+fn should_report_frequency_that_never_repeats() {
+    let day = solution::find(1).unwrap();
+    let limits = isolated(Duration::from_secs(30));
+    let run = runner::solve_day(&day, &[2], "./inputs/test/no_repeat.txt", &limits).unwrap();
+
+    match &run.parts[0].result {
+        Err(error) => assert!(error
+            .to_string()
+            .contains("No frequency is ever reached twice")),
+        _ => panic!("Expected no solution"),
+    }
+}
+
+#[test]
+fn should_report_child_errors() {
+    let day = solution::find(1).unwrap();
+    let limits = isolated(Duration::from_secs(30));
+    let run = runner::solve_day(&day, &[1], "./inputs/test/test.txt", &limits).unwrap();
+
+    let error = run.parts[0].result.as_ref().err().unwrap();
+    assert!(error.to_string().starts_with("./inputs/test/test.txt:1:"));
+}
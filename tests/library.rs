@@ -0,0 +1,38 @@
+use advent_of_code_2018::common::Point2;
+use advent_of_code_2018::solution::{self, Answer};
+use advent_of_code_2018::{common, day_1, day_3, day_5, day_6};
+
+#[test]
+fn should_solve_examples_through_public_functions() {
+    let changes = vec![String::from("+1"), String::from("-1")];
+    assert_eq!(0, day_1::calculate_first_repetition(0, changes).unwrap());
+
+    let claims = vec![
+        "#1 @ 1,3: 4x4".to_string(),
+        "#2 @ 3,1: 4x4".to_string(),
+        "#3 @ 5,5: 2x2".to_string(),
+    ];
+    let (covered, lone) = day_3::map_rectangles(&day_3::parse_rectangles(&claims).unwrap());
+    assert_eq!(4, day_3::count_overlap(&covered));
+    assert_eq!(Some(3), lone);
+
+    assert_eq!("dabCBAcaDA", day_5::process_polymer("dabAcCaCBAcCcaDA"));
+
+    let points: Vec<Point2> = [(1, 1), (1, 6), (8, 3), (3, 4), (5, 5), (8, 9)]
+        .iter()
+        .map(|p| Point2::from(*p))
+        .collect();
+    let closest = day_6::determine_closest_points(&points);
+    assert_eq!(Some(&17), day_6::finite_areas(&closest).get(&4));
+}
+
+#[test]
+fn should_run_registered_day_from_another_crate() {
+    let day = solution::find(1).unwrap();
+    let input = day.parse(common::read_lines("./inputs/day_1/input.txt").unwrap());
+
+    assert_eq!(
+        Answer::Integer(595),
+        day.solve(1, &input.unwrap()).unwrap().answer
+    );
+}
@@ -0,0 +1,62 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+/// Scaffolds a day in a copy of the crate, then builds and tests that copy. Its own target
+/// directory keeps it clear of the build that is running this test, and it only runs the library's
+/// tests, so it doesn't run this one again.
+#[test]
+fn should_scaffold_day_that_builds_and_passes_tests() {
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("scaffold");
+    let copy = root.join("crate");
+    if copy.exists() {
+        fs::remove_dir_all(&copy).unwrap();
+    }
+    copy_dir(Path::new("src"), &copy.join("src"));
+    copy_dir(Path::new("inputs"), &copy.join("inputs"));
+    copy_dir(Path::new("tests"), &copy.join("tests"));
+    for file in &["Cargo.toml", "Cargo.lock", "answers.toml"] {
+        if Path::new(file).exists() {
+            fs::copy(file, copy.join(file)).unwrap();
+        }
+    }
+
+    let scaffolded = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["new-day", "7", "--title", "The Sum of Its Parts"])
+        .current_dir(&copy)
+        .output()
+        .unwrap();
+    assert!(scaffolded.status.success(), "{:?}", scaffolded);
+
+    let tested = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["test", "--offline", "--lib"])
+        .current_dir(&copy)
+        .env("CARGO_TARGET_DIR", root.join("target"))
+        .env("CARGO_HUSKY_DONT_INSTALL_HOOKS", "1")
+        .output()
+        .unwrap();
+    let output = String::from_utf8_lossy(&tested.stdout);
+
+    assert!(
+        tested.status.success(),
+        "{}\n{}",
+        output,
+        String::from_utf8_lossy(&tested.stderr)
+    );
+    assert!(output.contains("day_7::tests::should_parse_line ... ok"));
+    assert!(output.contains("solution::tests::should_register_days_in_order ... ok"));
+}
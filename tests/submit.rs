@@ -0,0 +1,55 @@
+use advent_of_code_2018::fetch::Config;
+use advent_of_code_2018::submit::{self, Outcome};
+use common::mock_server;
+use std::time::Duration;
+mod common;
+
+fn config(base_url: String) -> Config {
+    Config {
+        base_url,
+        year: 2018,
+        session: Some("secret".to_string()),
+    }
+}
+
+#[test]
+fn should_post_answer() {
+    let (base_url, requests) = mock_server(
+        "200 OK",
+        "<article><p>That's the right answer!  You are one gold star closer.</p></article>",
+    );
+
+    assert_eq!(
+        Outcome::Correct,
+        submit::submit(&config(base_url), 2, 2, "fgij").unwrap()
+    );
+    assert_eq!(
+        vec![
+            "POST /2018/day/2/answer HTTP/1.1\nConnection: close\nCookie: session=secret\n\
+             Content-Type: application/x-www-form-urlencoded\nContent-Length: 19\n\n\
+             level=2&answer=fgij"
+        ],
+        *requests.lock().unwrap()
+    );
+}
+
+#[test]
+fn should_read_rate_limit() {
+    let (base_url, _) = mock_server(
+        "200 OK",
+        "<article><p>You gave an answer too recently; you have to wait after submitting an answer \
+         before trying again.  You have 1m 5s left to wait.</p></article>",
+    );
+
+    assert_eq!(
+        Outcome::RateLimited(Some(Duration::from_secs(65))),
+        submit::submit(&config(base_url), 1, 1, "595").unwrap()
+    );
+}
+
+#[test]
+fn should_fail_on_unrecognised_response() {
+    let (base_url, _) = mock_server("200 OK", "<p>Something else entirely</p>");
+
+    assert!(submit::submit(&config(base_url), 1, 1, "595").is_err());
+}